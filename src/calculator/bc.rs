@@ -0,0 +1,175 @@
+use thiserror::Error;
+use crate::models::Environment;
+use crate::calculator::drag::{DragModel, StandardDragModel};
+
+const MAX_BISECTION_ITERATIONS: usize = 100;
+const BC_SEARCH_RANGE: (f64, f64) = (0.001, 5.0);  // lb/in²
+const FLIGHT_STEP: f64 = 1.0;  // feet
+const MAX_FLIGHT_DISTANCE: f64 = 30000.0;  // feet
+
+/// Finds the BC against the `to` drag model that flies like `bc` against the `from` model
+/// over the velocity range (ft/s, either order): the bullet is flown flat from the upper to
+/// the lower velocity with the original BC, then the new BC is solved so that it covers the
+/// same distance in the same time. Uses the ICAO standard atmosphere BCs are referenced to.
+pub fn convert_ballistic_coefficient(
+    bc: f64,
+    from: StandardDragModel,
+    to: StandardDragModel,
+    velocity_range: (f64, f64),
+) -> Result<f64, BallisticCoefficientError> {
+    let (low, high) = if velocity_range.0 < velocity_range.1 {
+        velocity_range
+    } else {
+        (velocity_range.1, velocity_range.0)
+    };
+    if !bc.is_finite() || bc <= 0.0 {
+        return Err(BallisticCoefficientError::InvalidParameters);
+    }
+    if !low.is_finite() || low <= 0.0 || high - low < 1.0 {
+        return Err(BallisticCoefficientError::InvalidVelocityRange);
+    }
+
+    let environment = Environment::icao_standard(0.0);
+    let from_model = from.to_model();
+    let to_model = to.to_model();
+
+    let (distance, time) = fly_to_velocity(from_model.as_ref(), bc, &environment, high, low)
+        .ok_or(BallisticCoefficientError::InvalidVelocityRange)?;
+
+    // Time to cover the distance falls as the BC rises
+    bisect(BC_SEARCH_RANGE, |candidate| {
+        fly_distance(to_model.as_ref(), candidate, &environment, high, distance)
+            .map(|(candidate_time, _)| candidate_time - time)
+    })
+}
+
+/// Solves the BC against `drag_model` from chronograph velocities (ft/s) measured
+/// `distance` yards apart, e.g. at the muzzle and downrange, in the given atmosphere.
+pub fn ballistic_coefficient_from_velocities(
+    near_velocity: f64,
+    far_velocity: f64,
+    distance: f64,
+    environment: &Environment,
+    drag_model: &dyn DragModel,
+) -> Result<f64, BallisticCoefficientError> {
+    if !near_velocity.is_finite() || !far_velocity.is_finite() || far_velocity <= 0.0 || near_velocity <= far_velocity {
+        return Err(BallisticCoefficientError::InvalidVelocityRange);
+    }
+    if !distance.is_finite() || distance <= 0.0 {
+        return Err(BallisticCoefficientError::InvalidParameters);
+    }
+
+    // The retained velocity rises with the BC
+    bisect(BC_SEARCH_RANGE, |candidate| {
+        fly_distance(drag_model, candidate, environment, near_velocity, distance * 3.0)
+            .map(|(_, velocity)| far_velocity - velocity)
+    })
+}
+
+/// Bisects `f`, which must be decreasing, for its root within `range`.
+pub(crate) fn bisect(
+    range: (f64, f64),
+    f: impl Fn(f64) -> Option<f64>,
+) -> Result<f64, BallisticCoefficientError> {
+    let (mut low, mut high) = range;
+    // A bullet that never gets there is treated as infinitely slow
+    let value = |x: f64| f(x).unwrap_or(f64::INFINITY);
+    if value(low) < 0.0 || value(high) > 0.0 {
+        return Err(BallisticCoefficientError::NotConverged);
+    }
+
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let middle = 0.5 * (low + high);
+        if value(middle) > 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+        if high - low < 1e-6 * middle {
+            return Ok(0.5 * (low + high));
+        }
+    }
+
+    Err(BallisticCoefficientError::NotConverged)
+}
+
+/// Distance (ft) and time (s) for flat, drag-only flight to slow from `start` to `end` ft/s.
+pub(crate) fn fly_to_velocity(
+    model: &dyn DragModel,
+    bc: f64,
+    environment: &Environment,
+    start: f64,
+    end: f64,
+) -> Option<(f64, f64)> {
+    let (mut distance, mut velocity, mut time) = (0.0, start, 0.0);
+
+    while velocity > end {
+        if distance > MAX_FLIGHT_DISTANCE {
+            return None;
+        }
+        let (next_velocity, dt) = flight_step(model, bc, environment, velocity, FLIGHT_STEP);
+        if next_velocity <= end {
+            // Interpolate the last partial step
+            let fraction = (velocity - end) / (velocity - next_velocity);
+            return Some((distance + FLIGHT_STEP * fraction, time + dt * fraction));
+        }
+        distance += FLIGHT_STEP;
+        velocity = next_velocity;
+        time += dt;
+    }
+
+    Some((distance, time))
+}
+
+/// Time (s) and final velocity (ft/s) for flat, drag-only flight starting at `start` ft/s
+/// to cover `distance` feet.
+pub(crate) fn fly_distance(
+    model: &dyn DragModel,
+    bc: f64,
+    environment: &Environment,
+    start: f64,
+    distance: f64,
+) -> Option<(f64, f64)> {
+    let (mut covered, mut velocity, mut time) = (0.0, start, 0.0);
+
+    while covered < distance {
+        let step = FLIGHT_STEP.min(distance - covered);
+        let (next_velocity, dt) = flight_step(model, bc, environment, velocity, step);
+        if !next_velocity.is_finite() || next_velocity <= 0.0 {
+            return None;
+        }
+        covered += step;
+        velocity = next_velocity;
+        time += dt;
+    }
+
+    Some((time, velocity))
+}
+
+/// One RK4 step of `dx` feet on dv/dx = −a(v)/v and dt/dx = 1/v.
+fn flight_step(model: &dyn DragModel, bc: f64, environment: &Environment, velocity: f64, dx: f64) -> (f64, f64) {
+    let air_density = environment.air_density();
+    let speed_of_sound = environment.speed_of_sound();
+    let dv = |v: f64| -model.calculate_drag(v, air_density, speed_of_sound, bc) / v;
+
+    let k1 = dv(velocity);
+    let k2 = dv(velocity + k1 * dx / 2.0);
+    let k3 = dv(velocity + k2 * dx / 2.0);
+    let k4 = dv(velocity + k3 * dx);
+    let next = velocity + (k1 + 2.0 * k2 + 2.0 * k3 + k4) * dx / 6.0;
+
+    // Simpson's rule for the time spent on the step
+    let middle = velocity + (k1 + 2.0 * k2 + 2.0 * k3 + k4) * dx / 12.0;
+    let dt = dx / 6.0 * (1.0 / velocity + 4.0 / middle + 1.0 / next);
+    (next, dt)
+}
+
+#[derive(Debug, Error)]
+pub enum BallisticCoefficientError {
+    #[error("Ballistic coefficient and distance must be positive")]
+    InvalidParameters,
+    #[error("Velocities must be positive and decreasing, spanning at least 1 ft/s")]
+    InvalidVelocityRange,
+    #[error("No matching ballistic coefficient found")]
+    NotConverged,
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+use crate::calculator::drag::{DragModel, calculate_drag_common, interpolate_cd};
+
+/// A projectile-specific drag curve, e.g. from Doppler radar measurements.
+///
+/// Because the Cd values describe the actual bullet rather than a standard
+/// reference projectile, no ballistic coefficient is needed: the calculator
+/// scales drag by the projectile's sectional density instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "CustomDragTable")]
+pub struct CustomDragModel {
+    pub name: String,
+    table: Vec<(f64, f64)>,  // (Mach, Cd)
+}
+
+/// Serialized form of a drag curve, checked by `CustomDragModel::new` on the way in.
+#[derive(Deserialize)]
+struct CustomDragTable {
+    name: String,
+    table: Vec<(f64, f64)>,
+}
+
+impl TryFrom<CustomDragTable> for CustomDragModel {
+    type Error = CustomDragError;
+
+    fn try_from(data: CustomDragTable) -> Result<Self, Self::Error> {
+        Self::new(data.name, data.table)
+    }
+}
+
+impl CustomDragModel {
+    pub fn new(name: impl Into<String>, table: Vec<(f64, f64)>) -> Result<Self, CustomDragError> {
+        // Validate inputs
+        if table.len() < 2 {
+            return Err(CustomDragError::TooFewPoints);
+        }
+        if table.iter().any(|&(mach, cd)| !mach.is_finite() || mach < 0.0 || !cd.is_finite() || cd < 0.0) {
+            return Err(CustomDragError::InvalidPoint);
+        }
+        if table.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(CustomDragError::NonMonotonicMach);
+        }
+
+        Ok(Self {
+            name: name.into(),
+            table,
+        })
+    }
+
+    /// Loads a drag curve, choosing the format from the file extension
+    /// (`.csv`, `.json` or `.drg`).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CustomDragError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "csv" => Self::from_csv(name, &contents),
+            "json" => Self::from_json(&contents),
+            "drg" => Self::from_drg(&contents),
+            _ => Err(CustomDragError::UnsupportedFormat(extension)),
+        }
+    }
+
+    /// Parses `mach,cd` rows. A non-numeric header row and `#` comments are skipped.
+    pub fn from_csv(name: impl Into<String>, contents: &str) -> Result<Self, CustomDragError> {
+        let mut table = Vec::new();
+
+        for (index, line) in data_lines(contents).enumerate() {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            match parse_pair(&fields) {
+                Some(point) => table.push(point),
+                None if index == 0 => continue,  // Header row
+                None => return Err(CustomDragError::Parse(line.to_string())),
+            }
+        }
+
+        Self::new(name, table)
+    }
+
+    /// Parses the serialized form of this type: `{"name": ..., "table": [[mach, cd], ...]}`.
+    pub fn from_json(contents: &str) -> Result<Self, CustomDragError> {
+        let data: CustomDragTable = serde_json::from_str(contents)
+            .map_err(|e| CustomDragError::Parse(e.to_string()))?;
+        Self::new(data.name, data.table)
+    }
+
+    /// Parses the `.drg` format shared by several solvers: a `CFM` header line
+    /// describing the projectile followed by whitespace separated `cd mach` rows.
+    pub fn from_drg(contents: &str) -> Result<Self, CustomDragError> {
+        let mut lines = data_lines(contents);
+        let header = lines.next().ok_or(CustomDragError::TooFewPoints)?;
+
+        // Header is `CFM <mass kg> <diameter m> <description>`; keep the description as the name
+        let mut tokens = header.split_whitespace().peekable();
+        tokens.next_if_eq(&"CFM");
+        for _ in 0..2 {
+            tokens.next_if(|token| token.parse::<f64>().is_ok());
+        }
+        let name = tokens.collect::<Vec<_>>().join(" ");
+
+        let mut table = Vec::new();
+        for line in lines {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (cd, mach) = parse_pair(&fields).ok_or_else(|| CustomDragError::Parse(line.to_string()))?;
+            table.push((mach, cd));
+        }
+
+        Self::new(name, table)
+    }
+
+    pub fn table(&self) -> &[(f64, f64)] {
+        &self.table
+    }
+}
+
+fn data_lines(contents: &str) -> impl Iterator<Item = &str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn parse_pair(fields: &[&str]) -> Option<(f64, f64)> {
+    match fields {
+        [first, second] => Some((first.parse().ok()?, second.parse().ok()?)),
+        _ => None,
+    }
+}
+
+impl DragModel for CustomDragModel {
+    fn calculate_drag(&self, velocity: f64, air_density: f64, speed_of_sound: f64, ballistic_coefficient: f64) -> f64 {
+        let mach = velocity / speed_of_sound;
+        let cd = self.get_cd(mach);
+        calculate_drag_common(velocity, air_density, ballistic_coefficient, cd)
+    }
+
+    fn get_cd(&self, mach: f64) -> f64 {
+        interpolate_cd(&self.table, mach)
+    }
+
+    fn requires_ballistic_coefficient(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CustomDragError {
+    #[error("Failed to read drag file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported drag file format: {0:?}")]
+    UnsupportedFormat(String),
+    #[error("Could not parse drag data: {0}")]
+    Parse(String),
+    #[error("Drag curve needs at least two points")]
+    TooFewPoints,
+    #[error("Mach and Cd values must be finite and non-negative")]
+    InvalidPoint,
+    #[error("Mach values must be strictly increasing")]
+    NonMonotonicMach,
+}
@@ -1,62 +1,173 @@
 use std::f64;
+use serde::{Serialize, Deserialize};
+use crate::calculator::drag_tables::*;
 
 pub trait DragModel {
-    fn calculate_drag(&self, velocity: f64, air_density: f64, ballistic_coefficient: f64) -> f64;
+    /// Deceleration due to drag (ft/s²) for a velocity (ft/s), air density (lb/ft³),
+    /// speed of sound (ft/s) and ballistic coefficient (lb/in²).
+    fn calculate_drag(&self, velocity: f64, air_density: f64, speed_of_sound: f64, ballistic_coefficient: f64) -> f64;
     fn get_cd(&self, mach: f64) -> f64;
-}
 
-pub struct G1DragModel;
-pub struct G7DragModel;
+    /// Whether drag is scaled by the projectile's ballistic coefficient.
+    /// Models describing the actual bullet use its sectional density instead.
+    fn requires_ballistic_coefficient(&self) -> bool {
+        true
+    }
+}
 
-fn calculate_drag_common(velocity: f64, air_density: f64, ballistic_coefficient: f64, cd: f64) -> f64 {
-    let form_factor = 1.0 / (0.0001 + ballistic_coefficient);
-    0.5 * air_density * velocity.powi(2) * cd * form_factor
+/// Retardation of a projectile whose drag is the reference projectile's `cd`
+/// scaled by its ballistic coefficient: a = ρ·v²·Cd·A / (2m), where the
+/// BC (m/d², lb/in²) replaces m/A = 4·BC / π and 144 converts in² to ft².
+pub(crate) fn calculate_drag_common(velocity: f64, air_density: f64, ballistic_coefficient: f64, cd: f64) -> f64 {
+    air_density * velocity.powi(2) * cd * std::f64::consts::PI / (8.0 * 144.0 * ballistic_coefficient)
 }
 
-impl DragModel for G1DragModel {
-    fn calculate_drag(&self, velocity: f64, air_density: f64, ballistic_coefficient: f64) -> f64 {
-        let mach = velocity / 1116.4;  // Speed of sound at sea level in ft/s
-        let cd = self.get_cd(mach);
-        calculate_drag_common(velocity, air_density, ballistic_coefficient, cd)
+/// Interpolates Cd from a Mach-ascending (Mach, Cd) table using monotone
+/// piecewise cubic Hermite interpolation, clamping outside the table. The table
+/// must not be empty, which the standard tables and `CustomDragModel` guarantee.
+pub(crate) fn interpolate_cd(table: &[(f64, f64)], mach: f64) -> f64 {
+    let last = table.len() - 1;
+    if mach <= table[0].0 {
+        return table[0].1;
     }
+    if mach >= table[last].0 {
+        return table[last].1;
+    }
+
+    // Binary search for the segment containing mach
+    let i = table.partition_point(|&(m, _)| m <= mach) - 1;
+    let (x0, y0) = table[i];
+    let (x1, y1) = table[i + 1];
+    let h = x1 - x0;
+    let slope = |k: usize| (table[k + 1].1 - table[k].1) / (table[k + 1].0 - table[k].0);
 
-    fn get_cd(&self, mach: f64) -> f64 {
-        if mach <= 0.7 {
-            0.225
-        } else if mach <= 0.89 {
-            0.225 + (mach - 0.7) * (0.275 - 0.225) / (0.89 - 0.7)
-        } else if mach <= 1.0 {
-            0.275 + (mach - 0.89) * (0.425 - 0.275) / (1.0 - 0.89)
-        } else if mach <= 1.1 {
-            0.425 + (mach - 1.0) * (0.295 - 0.425) / (1.1 - 1.0)
-        } else if mach <= 1.2 {
-            0.295 + (mach - 1.1) * (0.280 - 0.295) / (1.2 - 1.1)
-        } else {
-            0.280
+    // Fritsch-Carlson tangents keep the curve from overshooting between points
+    let tangent = |k: usize| -> f64 {
+        if k == 0 {
+            return slope(0);
         }
-    }
+        if k == last {
+            return slope(last - 1);
+        }
+        let (d0, d1) = (slope(k - 1), slope(k));
+        if d0 * d1 <= 0.0 {
+            return 0.0;
+        }
+        let (h0, h1) = (table[k].0 - table[k - 1].0, table[k + 1].0 - table[k].0);
+        let (w0, w1) = (2.0 * h1 + h0, h1 + 2.0 * h0);
+        (w0 + w1) / (w0 / d0 + w1 / d1)
+    };
+    let (m0, m1) = (tangent(i), tangent(i + 1));
+
+    let t = (mach - x0) / h;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+        + (t3 - 2.0 * t2 + t) * h * m0
+        + (-2.0 * t3 + 3.0 * t2) * y1
+        + (t3 - t2) * h * m1
 }
 
-impl DragModel for G7DragModel {
-    fn calculate_drag(&self, velocity: f64, air_density: f64, ballistic_coefficient: f64) -> f64 {
-        let mach = velocity / 1116.4;
-        let cd = self.get_cd(mach);
-        calculate_drag_common(velocity, air_density, ballistic_coefficient, cd)
+macro_rules! standard_drag_model {
+    ($name:ident, $table:ident) => {
+        pub struct $name;
+
+        impl DragModel for $name {
+            fn calculate_drag(&self, velocity: f64, air_density: f64, speed_of_sound: f64, ballistic_coefficient: f64) -> f64 {
+                let mach = velocity / speed_of_sound;
+                let cd = self.get_cd(mach);
+                calculate_drag_common(velocity, air_density, ballistic_coefficient, cd)
+            }
+
+            fn get_cd(&self, mach: f64) -> f64 {
+                interpolate_cd($table, mach)
+            }
+        }
+    };
+}
+
+standard_drag_model!(G1DragModel, G1_TABLE);
+standard_drag_model!(G2DragModel, G2_TABLE);
+standard_drag_model!(G5DragModel, G5_TABLE);
+standard_drag_model!(G6DragModel, G6_TABLE);
+standard_drag_model!(G7DragModel, G7_TABLE);
+standard_drag_model!(G8DragModel, G8_TABLE);
+standard_drag_model!(GIDragModel, GI_TABLE);
+standard_drag_model!(GSDragModel, GS_TABLE);
+standard_drag_model!(GLDragModel, GL_TABLE);
+standard_drag_model!(RA4DragModel, RA4_TABLE);
+
+/// Selector for the standard drag functions, e.g. for UI choices or saved settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StandardDragModel {
+    G1,
+    G2,
+    G5,
+    G6,
+    G7,
+    G8,
+    GI,
+    GS,
+    GL,
+    RA4,
+}
+
+impl StandardDragModel {
+    pub const ALL: [StandardDragModel; 10] = [
+        StandardDragModel::G1,
+        StandardDragModel::G2,
+        StandardDragModel::G5,
+        StandardDragModel::G6,
+        StandardDragModel::G7,
+        StandardDragModel::G8,
+        StandardDragModel::GI,
+        StandardDragModel::GS,
+        StandardDragModel::GL,
+        StandardDragModel::RA4,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StandardDragModel::G1 => "G1",
+            StandardDragModel::G2 => "G2",
+            StandardDragModel::G5 => "G5",
+            StandardDragModel::G6 => "G6",
+            StandardDragModel::G7 => "G7",
+            StandardDragModel::G8 => "G8",
+            StandardDragModel::GI => "GI",
+            StandardDragModel::GS => "GS",
+            StandardDragModel::GL => "GL",
+            StandardDragModel::RA4 => "RA4",
+        }
     }
 
-    fn get_cd(&self, mach: f64) -> f64 {
-        if mach <= 0.7 {
-            0.1198
-        } else if mach <= 0.89 {
-            0.1198 + (mach - 0.7) * (0.1575 - 0.1198) / (0.89 - 0.7)
-        } else if mach <= 1.0 {
-            0.1575 + (mach - 0.89) * (0.2255 - 0.1575) / (1.0 - 0.89)
-        } else if mach <= 1.1 {
-            0.2255 + (mach - 1.0) * (0.1955 - 0.2255) / (1.1 - 1.0)
-        } else if mach <= 1.2 {
-            0.1955 + (mach - 1.1) * (0.1880 - 0.1955) / (1.2 - 1.1)
-        } else {
-            0.1880
+    pub fn table(&self) -> &'static [(f64, f64)] {
+        match self {
+            StandardDragModel::G1 => G1_TABLE,
+            StandardDragModel::G2 => G2_TABLE,
+            StandardDragModel::G5 => G5_TABLE,
+            StandardDragModel::G6 => G6_TABLE,
+            StandardDragModel::G7 => G7_TABLE,
+            StandardDragModel::G8 => G8_TABLE,
+            StandardDragModel::GI => GI_TABLE,
+            StandardDragModel::GS => GS_TABLE,
+            StandardDragModel::GL => GL_TABLE,
+            StandardDragModel::RA4 => RA4_TABLE,
         }
     }
-}
\ No newline at end of file
+
+    pub fn to_model(&self) -> Box<dyn DragModel> {
+        match self {
+            StandardDragModel::G1 => Box::new(G1DragModel),
+            StandardDragModel::G2 => Box::new(G2DragModel),
+            StandardDragModel::G5 => Box::new(G5DragModel),
+            StandardDragModel::G6 => Box::new(G6DragModel),
+            StandardDragModel::G7 => Box::new(G7DragModel),
+            StandardDragModel::G8 => Box::new(G8DragModel),
+            StandardDragModel::GI => Box::new(GIDragModel),
+            StandardDragModel::GS => Box::new(GSDragModel),
+            StandardDragModel::GL => Box::new(GLDragModel),
+            StandardDragModel::RA4 => Box::new(RA4DragModel),
+        }
+    }
+}
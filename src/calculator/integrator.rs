@@ -0,0 +1,145 @@
+use nalgebra::SVector;
+use serde::{Serialize, Deserialize};
+
+/// Projectile state: position (ft) followed by velocity (ft/s), each as
+/// downrange, vertical and lateral (positive right) components.
+pub type State = SVector<f64, 6>;
+
+/// Result of advancing the state by one integrator step.
+pub struct Step {
+    pub state: State,
+    pub dt: f64,       // step actually taken (s)
+    pub next_dt: f64,  // suggested size of the following step (s)
+}
+
+pub trait Integrator {
+    fn step(&self, derivative: &dyn Fn(&State) -> State, state: &State, dt: f64) -> Step;
+}
+
+/// Explicit forward Euler: first order, cheapest per step.
+pub struct EulerIntegrator;
+
+/// Classic fourth-order Runge-Kutta with a fixed step.
+pub struct RungeKutta4Integrator;
+
+/// Adaptive Dormand-Prince 5(4) that shrinks or grows the step to keep the
+/// estimated local error within `tolerance`.
+pub struct DormandPrinceIntegrator {
+    pub tolerance: f64,
+    pub min_step: f64,  // seconds
+}
+
+impl Default for DormandPrinceIntegrator {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-8,
+            min_step: 1e-6,
+        }
+    }
+}
+
+impl Integrator for EulerIntegrator {
+    fn step(&self, derivative: &dyn Fn(&State) -> State, state: &State, dt: f64) -> Step {
+        Step {
+            state: state + derivative(state) * dt,
+            dt,
+            next_dt: dt,
+        }
+    }
+}
+
+impl Integrator for RungeKutta4Integrator {
+    fn step(&self, derivative: &dyn Fn(&State) -> State, state: &State, dt: f64) -> Step {
+        let k1 = derivative(state);
+        let k2 = derivative(&(state + k1 * (dt / 2.0)));
+        let k3 = derivative(&(state + k2 * (dt / 2.0)));
+        let k4 = derivative(&(state + k3 * dt));
+
+        Step {
+            state: state + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (dt / 6.0),
+            dt,
+            next_dt: dt,
+        }
+    }
+}
+
+impl Integrator for DormandPrinceIntegrator {
+    fn step(&self, derivative: &dyn Fn(&State) -> State, state: &State, dt: f64) -> Step {
+        let mut dt = dt;
+
+        loop {
+            let k1 = derivative(state);
+            let k2 = derivative(&(state + k1 * (dt / 5.0)));
+            let k3 = derivative(&(state + (k1 * (3.0 / 40.0) + k2 * (9.0 / 40.0)) * dt));
+            let k4 = derivative(&(state + (k1 * (44.0 / 45.0) - k2 * (56.0 / 15.0) + k3 * (32.0 / 9.0)) * dt));
+            let k5 = derivative(&(state
+                + (k1 * (19372.0 / 6561.0) - k2 * (25360.0 / 2187.0) + k3 * (64448.0 / 6561.0)
+                    - k4 * (212.0 / 729.0)) * dt));
+            let k6 = derivative(&(state
+                + (k1 * (9017.0 / 3168.0) - k2 * (355.0 / 33.0) + k3 * (46732.0 / 5247.0)
+                    + k4 * (49.0 / 176.0) - k5 * (5103.0 / 18656.0)) * dt));
+
+            // Fifth-order solution
+            let next = state
+                + (k1 * (35.0 / 384.0) + k3 * (500.0 / 1113.0) + k4 * (125.0 / 192.0)
+                    - k5 * (2187.0 / 6784.0) + k6 * (11.0 / 84.0)) * dt;
+            let k7 = derivative(&next);
+
+            // Difference between the fifth- and embedded fourth-order solutions
+            let error_estimate = (k1 * (71.0 / 57600.0) - k3 * (71.0 / 16695.0) + k4 * (71.0 / 1920.0)
+                - k5 * (17253.0 / 339200.0) + k6 * (22.0 / 525.0) - k7 * (1.0 / 40.0)) * dt;
+
+            // Mixed absolute/relative error norm
+            let error = error_estimate
+                .iter()
+                .zip(next.iter())
+                .map(|(e, y)| e.abs() / (self.tolerance * (1.0 + y.abs())))
+                .fold(0.0, f64::max);
+
+            let scale = if error > 0.0 { 0.9 * error.powf(-0.2) } else { 5.0 };
+            let next_dt = dt * scale.clamp(0.2, 5.0);
+
+            if error <= 1.0 || dt <= self.min_step {
+                return Step {
+                    state: next,
+                    dt,
+                    next_dt,
+                };
+            }
+
+            dt = next_dt.max(self.min_step);
+        }
+    }
+}
+
+/// Selector for the built-in integrators, e.g. for UI choices or saved settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntegrationMethod {
+    Euler,
+    RungeKutta4,
+    DormandPrince,
+}
+
+impl IntegrationMethod {
+    pub const ALL: [IntegrationMethod; 3] = [
+        IntegrationMethod::Euler,
+        IntegrationMethod::RungeKutta4,
+        IntegrationMethod::DormandPrince,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntegrationMethod::Euler => "Euler",
+            IntegrationMethod::RungeKutta4 => "RK4",
+            IntegrationMethod::DormandPrince => "RK45",
+        }
+    }
+
+    pub fn to_integrator(&self) -> Box<dyn Integrator> {
+        match self {
+            IntegrationMethod::Euler => Box::new(EulerIntegrator),
+            IntegrationMethod::RungeKutta4 => Box::new(RungeKutta4Integrator),
+            IntegrationMethod::DormandPrince => Box::new(DormandPrinceIntegrator::default()),
+        }
+    }
+}
@@ -1,5 +1,17 @@
+mod bc;
+mod custom_drag;
 mod drag;
+mod drag_tables;
+mod integrator;
 mod physics;
+mod truing;
+mod zeroing;
 
+pub use bc::*;
+pub use custom_drag::*;
 pub use drag::*;
-pub use physics::*;
\ No newline at end of file
+pub use drag_tables::*;
+pub use integrator::*;
+pub use physics::*;
+pub use truing::*;
+pub use zeroing::*;
@@ -1,9 +1,26 @@
-use crate::models::{Projectile, Environment, TrajectoryPoint};
+use nalgebra::Vector3;
+use thiserror::Error;
+use crate::models::{Projectile, ProjectileError, Environment, TrajectoryPoint, Twist, TwistDirection, StabilityWarning, PowderSensitivity, Barrel, Profile};
 use crate::calculator::drag::DragModel;
+use crate::calculator::integrator::{Integrator, RungeKutta4Integrator, State};
+use crate::calculator::truing::TruingParameter;
+use crate::calculator::zeroing::{ZeroSettings, ZeroError};
+
+const EARTH_ROTATION_RATE: f64 = 7.292115e-5;  // rad/s
+const OUTPUT_TIME_TOLERANCE: f64 = 1e-9;  // s, for output times that fall on a step end
+
 pub struct TrajectoryCalculator {
     projectile: Projectile,
     environment: Environment,
     drag_model: Box<dyn DragModel>,
+    integrator: Box<dyn Integrator>,
+    zero: Option<ZeroSettings>,
+    azimuth: f64,  // degrees clockwise from true north
+    twist: Option<Twist>,
+    aerodynamic_jump: bool,
+    line_of_sight_angle: f64,  // degrees, positive uphill
+    cant: f64,  // degrees, positive clockwise
+    drag_scale: f64,  // multiplies the drag model, e.g. from truing
 }
 
 impl TrajectoryCalculator {
@@ -16,70 +33,418 @@ impl TrajectoryCalculator {
             projectile,
             environment,
             drag_model,
+            integrator: Box::new(RungeKutta4Integrator),
+            zero: None,
+            azimuth: 0.0,
+            twist: None,
+            aerodynamic_jump: true,
+            line_of_sight_angle: 0.0,
+            cant: 0.0,
+            drag_scale: 1.0,
         }
     }
-    
+
+    /// Scales the drag model's deceleration, e.g. to true the solver to observed
+    /// drops. A scale of 0.95 flies like a BC 5% higher.
+    pub fn with_drag_scale(mut self, drag_scale: f64) -> Self {
+        self.drag_scale = drag_scale;
+        self
+    }
+
+    /// Builds a zeroed calculator for a saved rifle, scope and load in the day's
+    /// conditions. The muzzle velocity is corrected for the powder temperature and
+    /// the rifle's barrel when the ammunition describes how it was measured.
+    pub fn from_profile(profile: &Profile, environment: Environment) -> Result<Self, CalculatorError> {
+        let ammunition = &profile.ammunition;
+        let zero = ZeroSettings::new(profile.scope.sight_height, profile.scope.zero_range)?;
+        let powder_temperature = environment.powder_temperature();
+
+        let mut calculator = Self::new(ammunition.projectile.clone(), environment, ammunition.to_drag_model())
+            .with_twist(profile.rifle.twist)
+            .with_zero(zero);
+
+        if let Some(sensitivity) = &ammunition.powder_sensitivity {
+            calculator = calculator.with_powder_temperature(sensitivity, powder_temperature)?;
+        }
+        if let Some(test_barrel_length) = ammunition.test_barrel_length {
+            calculator = calculator.with_barrel(&profile.rifle.barrel, test_barrel_length)?;
+        }
+
+        Ok(calculator)
+    }
+
+    /// Sets the muzzle velocity from the load's powder temperature sensitivity with
+    /// the ammunition at `powder_temperature` °F. Fails if the sensitivity, extended to
+    /// that temperature, leaves no positive velocity.
+    pub fn with_powder_temperature(mut self, sensitivity: &PowderSensitivity, powder_temperature: f64) -> Result<Self, ProjectileError> {
+        self.set_muzzle_velocity(sensitivity.muzzle_velocity_at(powder_temperature))?;
+        Ok(self)
+    }
+
+    /// Adjusts the muzzle velocity, quoted for a `reference_length` inch test barrel,
+    /// to this barrel. Apply after `with_powder_temperature`, which replaces the velocity.
+    /// Fails if the rule leaves no positive velocity, e.g. for a very short barrel.
+    pub fn with_barrel(mut self, barrel: &Barrel, reference_length: f64) -> Result<Self, ProjectileError> {
+        self.set_muzzle_velocity(self.projectile.muzzle_velocity + barrel.velocity_change(reference_length))?;
+        Ok(self)
+    }
+
+    fn set_muzzle_velocity(&mut self, muzzle_velocity: f64) -> Result<(), ProjectileError> {
+        if !muzzle_velocity.is_finite() || muzzle_velocity <= 0.0 {
+            return Err(ProjectileError::InvalidMuzzleVelocity(muzzle_velocity));
+        }
+        self.projectile.muzzle_velocity = muzzle_velocity;
+        Ok(())
+    }
+
+    /// Sets the rifle cant (degrees, positive clockwise as seen by the shooter).
+    pub fn with_cant(mut self, cant: f64) -> Self {
+        self.cant = cant;
+        self
+    }
+
+    /// Sets the line of sight angle (degrees, positive uphill) for inclined and
+    /// declined shots. The zero is still solved on flat ground. Fails unless the
+    /// angle is within ±90°, short of straight up or down.
+    pub fn with_line_of_sight_angle(mut self, line_of_sight_angle: f64) -> Result<Self, CalculatorError> {
+        if !line_of_sight_angle.is_finite() || line_of_sight_angle.abs() >= 90.0 {
+            return Err(CalculatorError::InvalidLineOfSightAngle(line_of_sight_angle));
+        }
+        self.line_of_sight_angle = line_of_sight_angle;
+        Ok(self)
+    }
+
+    /// Enables or disables the vertical aerodynamic jump caused by crosswind
+    /// at the muzzle. It is on by default and requires the twist to be known.
+    pub fn with_aerodynamic_jump(mut self, enabled: bool) -> Self {
+        self.aerodynamic_jump = enabled;
+        self
+    }
+
+    /// Sets the barrel twist, enabling spin drift.
+    pub fn with_twist(mut self, twist: Twist) -> Self {
+        self.twist = Some(twist);
+        self
+    }
+
+    /// Sets the direction of fire (degrees clockwise from true north) used for
+    /// the vertical Coriolis (Eötvös) effect.
+    pub fn with_azimuth(mut self, azimuth: f64) -> Self {
+        self.azimuth = azimuth;
+        self
+    }
+
+    /// Replaces the default RK4 integrator.
+    pub fn with_integrator(mut self, integrator: Box<dyn Integrator>) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Zeroes the rifle so that trajectories are reported relative to the line of sight.
+    pub fn with_zero(mut self, zero: ZeroSettings) -> Self {
+        self.zero = Some(zero);
+        self
+    }
+
+    pub fn projectile(&self) -> &Projectile {
+        &self.projectile
+    }
+
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    pub fn zero(&self) -> Option<&ZeroSettings> {
+        self.zero.as_ref()
+    }
+
+    pub fn drag_scale(&self) -> f64 {
+        self.drag_scale
+    }
+
+    /// The value a truing fit adjusts.
+    pub(crate) fn truing_parameter_mut(&mut self, parameter: TruingParameter) -> &mut f64 {
+        match parameter {
+            TruingParameter::MuzzleVelocity => &mut self.projectile.muzzle_velocity,
+            TruingParameter::DragScale => &mut self.drag_scale,
+        }
+    }
+
+    /// Miller stability factor in the shot's atmosphere, if the twist is known.
+    pub fn stability(&self) -> Option<f64> {
+        self.twist
+            .as_ref()
+            .map(|twist| self.projectile.miller_stability(twist, &self.environment))
+    }
+
+    pub fn stability_warning(&self) -> Option<StabilityWarning> {
+        self.stability().and_then(StabilityWarning::from_stability)
+    }
+
+    /// Trajectory out to `range_yards` with a point every `step_size` seconds. Fixed-step
+    /// integrators also step by `step_size`; adaptive ones choose their own steps.
+    /// Fails if the drag model needs a BC the projectile lacks, or the zero cannot be solved.
     pub fn calculate_trajectory(
         &self,
         range_yards: f64,
         step_size: f64
+    ) -> Result<Vec<TrajectoryPoint>, CalculatorError> {
+        if self.drag_model.requires_ballistic_coefficient() && self.projectile.ballistic_coefficient.is_none() {
+            return Err(ProjectileError::MissingBallisticCoefficient.into());
+        }
+
+        let (launch_angle, sight_height) = match &self.zero {
+            Some(zero) => (self.solve_zero_angle(zero, step_size)?, zero.sight_height),
+            None => (0.0, 0.0),
+        };
+
+        // Convert range to feet
+        let range_feet = range_yards * 3.0;
+
+        Ok(self.integrate(
+            &self.environment,
+            launch_angle,
+            sight_height,
+            range_feet,
+            step_size,
+            false
+        ))
+    }
+
+    /// Integrates the trajectory for a bore elevated by `launch_angle` (radians) above the line
+    /// of sight and `sight_height` inches below it. Distances are measured along the line of
+    /// sight and drop perpendicular to it. When `zeroing`, the shot is fired level on flat
+    /// ground without Coriolis deflection or aerodynamic jump, which the zero must not absorb.
+    pub(crate) fn integrate(
+        &self,
+        environment: &Environment,
+        launch_angle: f64,
+        sight_height: f64,
+        range_feet: f64,
+        step_size: f64,
+        zeroing: bool
     ) -> Vec<TrajectoryPoint> {
         let mut points = Vec::new();
+        let line_of_sight_angle = if zeroing { 0.0 } else { self.line_of_sight_angle.to_radians() };
+        let cant = if zeroing { 0.0 } else { self.cant };
         let g = 32.174;  // Acceleration due to gravity in ft/s²
-        
-        // Initial conditions
-        let mut x = 0.0;  // Distance traveled (feet)
-        let mut y = 0.0;  // Height (feet)
-        let mut vx = self.projectile.muzzle_velocity;  // Initial velocity x component (ft/s)
-        let mut vy = 0.0;  // Initial velocity y component (ft/s)
+
+        // Initial conditions: position (feet) relative to the bore, velocity (ft/s)
+        let mut state = to_state(
+            &Vector3::zeros(),
+            &Vector3::new(
+                self.projectile.muzzle_velocity * (line_of_sight_angle + launch_angle).cos(),
+                self.projectile.muzzle_velocity * (line_of_sight_angle + launch_angle).sin(),
+                0.0,
+            ),
+        );
+        let gravity = Vector3::new(0.0, -g, 0.0);
         let mut time = 0.0;
+        let mut dt = step_size;
 
-        // Convert range to feet
-        let range_feet = range_yards * 3.0;
-        
-        // Wind components
-        let wind_angle_rad = self.environment.wind_angle * std::f64::consts::PI / 180.0;
-        let wind_vx = self.environment.wind_speed * wind_angle_rad.cos();
-        let wind_vy = self.environment.wind_speed * wind_angle_rad.sin();
-
-        while x <= range_feet && y >= -1000.0 {  // Stop if drop exceeds 1000 feet
-            // Calculate current velocity magnitude relative to air
-            let v_rel_x = vx - wind_vx;
-            let v_rel_y = vy - wind_vy;
-            let v_total = (v_rel_x * v_rel_x + v_rel_y * v_rel_y).sqrt();
-
-            // Calculate drag force
-            let air_density = self.environment.air_density();
+        // Line of sight origin, offset from the bore perpendicular to the sight line (feet),
+        // and its unit vectors along and perpendicular to the sight line. Gravity stays vertical.
+        let sight_height_feet = sight_height / 12.0;
+        let (los_sin, los_cos) = line_of_sight_angle.sin_cos();
+        let sight_origin = Vector3::new(-sight_height_feet * los_sin, sight_height_feet * los_cos, 0.0);
+        let los_along = Vector3::new(los_cos, los_sin, 0.0);
+        let los_up = Vector3::new(-los_sin, los_cos, 0.0);
+
+        // Drag scaling: BC for standard drag functions, sectional density for bullet-specific curves
+        let ballistic_coefficient = self.projectile.ballistic_coefficient
+            .as_ref()
+            .filter(|_| self.drag_model.requires_ballistic_coefficient());
+        let sectional_density = self.projectile.sectional_density();
+
+        // Wind at the muzzle, which sets the aerodynamic jump
+        let muzzle_wind = environment.wind_vector();
+
+        let derivative = |s: &State| -> State {
+            let velocity = velocity_of(s);
+            let height = s[1];
+
+            // Local atmosphere at the bullet's height above the firing point
+            let air_density = environment.air_density_at(height);
+            let speed_of_sound = environment.speed_of_sound_at(height);
+
+            // Wind acts through the air-relative velocity: headwind and tailwind change the
+            // downrange drag while crosswind drags the bullet sideways. It is looked up at the
+            // bullet's downrange position so that each wind zone acts over its own segment.
+            let v_rel = velocity - environment.wind_vector_at(s[0]);
+            let v_total = v_rel.norm();
+
+            // Velocity-banded BCs step down as the bullet slows
+            let ballistic_coefficient = ballistic_coefficient.map_or(sectional_density, |bc| bc.at_velocity(v_total));
+
+            // Calculate drag deceleration, acting opposite the air-relative velocity
             let drag = self.drag_model.calculate_drag(
-                v_total, 
-                air_density, 
-                self.projectile.ballistic_coefficient
+                v_total,
+                air_density,
+                speed_of_sound,
+                ballistic_coefficient
             );
-            
-            // Calculate acceleration components
-            let ax = -(drag * v_rel_x) / (v_total * self.projectile.weight_grains);
-            let ay = -g - (drag * v_rel_y) / (v_total * self.projectile.weight_grains);
-
-            // Update velocities (RK4 would be more accurate, but this is sufficient for now)
-            vx += ax * step_size;
-            vy += ay * step_size;
-
-            // Update positions
-            x += vx * step_size;
-            y += vy * step_size;
-            time += step_size;
-
-            // Record point (converting back to yards/inches)
-            points.push(TrajectoryPoint::new(
-                x / 3.0,             // Convert feet to yards
-                y * 12.0,            // Convert feet to inches
-                (wind_vy * time) * 12.0,  // Windage in inches
-                v_total,             // Velocity in ft/s
-                0.5 * (self.projectile.weight_grains / 7000.0) * v_total * v_total / 32.174,  // Energy in ft-lbs
-                time
-            ));
+
+            let acceleration = gravity - v_rel * (drag * self.drag_scale / v_total);
+            to_state(&velocity, &acceleration)
+        };
+
+        // Coriolis accelerations for mostly horizontal flight are 2Ω·sin(latitude)·vx to the
+        // right and 2Ω·cos(latitude)·sin(azimuth)·vx upwards (Eötvös), so each deflection is
+        // that coefficient times the time integral of downrange distance.
+        let latitude_rad = environment.latitude.to_radians();
+        let azimuth_rad = self.azimuth.to_radians();
+        let coriolis_scale = if zeroing { 0.0 } else { 2.0 * EARTH_ROTATION_RATE };
+        let coriolis_horizontal_rate = coriolis_scale * latitude_rad.sin();
+        let coriolis_vertical_rate = coriolis_scale * latitude_rad.cos() * azimuth_rad.sin();
+        let mut distance_time_integral = 0.0;  // ft·s
+
+        // Litz spin drift approximation: 1.25·(Sg + 1.2)·t^1.83 inches in the direction of twist
+        let spin_drift_factor = match &self.twist {
+            Some(twist) => {
+                let sign = match twist.direction {
+                    TwistDirection::Right => 1.0,
+                    TwistDirection::Left => -1.0,
+                };
+                sign * 1.25 * (self.projectile.miller_stability(twist, environment) + 1.2)
+            }
+            None => 0.0,
+        };
+
+        // Litz aerodynamic jump: (0.01·Sg − 0.0024·L + 0.032) MOA per mph of crosswind, L in
+        // calibers. With right-hand twist a wind from the left throws the bullet up.
+        let aerodynamic_jump_moa = match &self.twist {
+            Some(twist) if self.aerodynamic_jump && !zeroing => {
+                let sg = self.projectile.miller_stability(twist, environment);
+                let length_calibers = self.projectile.length / self.projectile.caliber;
+                let crosswind_mph = muzzle_wind.z * 3600.0 / 5280.0;
+                let sign = match twist.direction {
+                    TwistDirection::Right => 1.0,
+                    TwistDirection::Left => -1.0,
+                };
+                sign * (0.01 * sg - 0.0024 * length_calibers + 0.032) * crosswind_mph
+            }
+            _ => 0.0,
+        };
+
+        // Vertical wind drift by the lag rule: the wind speed times the time lost to drag
+        // relative to flight at the muzzle velocity, projected perpendicular to the line of sight.
+        // The integrated drop already carries the wind, so this estimate is reported beside it.
+        let vertical_wind_fps = environment.vertical_wind_fps();
+        let muzzle_downrange_velocity = state[3];
+
+        let mut slant_range = 0.0;  // feet along the line of sight
+        let mut line_of_sight_height = 0.0;  // feet perpendicular to the line of sight
+
+        // Points are reported every `step_size` seconds whatever step the integrator takes,
+        // so an adaptive integrator can stride past several of them
+        let mut output_index = 1;
+        let mut output_time = step_size;
+        let mut last_output = (0.0, 0.0);  // time (s) and downrange distance (ft)
+
+        while slant_range <= range_feet && line_of_sight_height >= -1000.0 {  // Stop if drop exceeds 1000 feet
+            let step = self.integrator.step(&derivative, &state, dt);
+            let step_end = time + step.dt;
+            let mut slopes = None;
+
+            while output_time <= step_end + OUTPUT_TIME_TOLERANCE && slant_range <= range_feet && line_of_sight_height >= -1000.0 {
+                let fraction = (output_time - time) / step.dt;
+                let output = if fraction >= 1.0 - OUTPUT_TIME_TOLERANCE {
+                    step.state
+                } else {
+                    let (start_slope, end_slope) = *slopes.get_or_insert_with(|| (derivative(&state), derivative(&step.state)));
+                    hermite(&state, &step.state, &start_slope, &end_slope, step.dt, fraction)
+                };
+                distance_time_integral += 0.5 * (last_output.1 + output[0]) * (output_time - last_output.0);
+                last_output = (output_time, output[0]);
+
+                let from_sight = Vector3::new(output[0], output[1], 0.0) - sight_origin;
+                slant_range = from_sight.dot(&los_along);
+                line_of_sight_height = from_sight.dot(&los_up);
+
+                let velocity = velocity_of(&output).norm();
+                let speed_of_sound = environment.speed_of_sound_at(output[1]);
+
+                let coriolis_vertical = coriolis_vertical_rate * distance_time_integral * 12.0;
+                let coriolis_horizontal = coriolis_horizontal_rate * distance_time_integral * 12.0;
+                let spin_drift = spin_drift_factor * output_time.powf(1.83);
+                let aerodynamic_jump = aerodynamic_jump_moa * 1.047 * slant_range / 300.0;
+                let vertical_wind = vertical_wind_fps * (output_time - output[0] / muzzle_downrange_velocity) * los_cos * 12.0;
+
+                // Bore line height above the line of sight from the zero alone
+                let bore_line_height = (slant_range * launch_angle.tan() - sight_height_feet) * 12.0;
+
+                // Record point (converting back to yards/inches)
+                let mut point = TrajectoryPoint {
+                    coriolis_vertical,
+                    coriolis_horizontal,
+                    spin_drift,
+                    aerodynamic_jump,
+                    horizontal_distance: output[0] / 3.0,
+                    vertical_wind,
+                    ..TrajectoryPoint::new(
+                        slant_range / 3.0,   // Convert feet to yards
+                        line_of_sight_height * 12.0 + coriolis_vertical + aerodynamic_jump,  // Height relative to line of sight in inches
+                        output[2] * 12.0 + coriolis_horizontal + spin_drift,  // Windage in inches
+                        velocity,            // Velocity in ft/s
+                        0.5 * (self.projectile.weight_grains / 7000.0) * velocity * velocity / 32.174,  // Energy in ft-lbs
+                        output_time,
+                        velocity / speed_of_sound  // Mach number
+                    )
+                };
+
+                // Cant rotates the zero elevation plus the correction dialed for this range about the sight axis
+                point.elevation_correction = bore_line_height - point.drop;
+                let (cant_vertical, cant_horizontal) = point.cant_shift(cant);
+                point.cant_vertical = cant_vertical;
+                point.cant_horizontal = cant_horizontal;
+                point.drop += cant_vertical;
+                point.windage += cant_horizontal;
+
+                points.push(point);
+
+                output_index += 1;
+                output_time = output_index as f64 * step_size;
+            }
+
+            state = step.state;
+            time = step_end;
+            dt = step.next_dt;
+
+            // A steep shot that falls back before the range is reached never gets there
+            if velocity_of(&state).dot(&los_along) <= 0.0 {
+                break;
+            }
         }
-        
+
         points
     }
-}
\ No newline at end of file
+}
+
+fn to_state(position: &Vector3<f64>, velocity: &Vector3<f64>) -> State {
+    State::new(position.x, position.y, position.z, velocity.x, velocity.y, velocity.z)
+}
+
+fn velocity_of(state: &State) -> Vector3<f64> {
+    Vector3::new(state[3], state[4], state[5])
+}
+
+/// Cubic Hermite interpolation of the state a `fraction` of the way through a step of
+/// `dt` seconds, from the states and their derivatives at both ends.
+fn hermite(start: &State, end: &State, start_slope: &State, end_slope: &State, dt: f64, fraction: f64) -> State {
+    let t = fraction;
+    let (t2, t3) = (t * t, t * t * t);
+    start * (2.0 * t3 - 3.0 * t2 + 1.0)
+        + start_slope * ((t3 - 2.0 * t2 + t) * dt)
+        + end * (-2.0 * t3 + 3.0 * t2)
+        + end_slope * ((t3 - t2) * dt)
+}
+
+#[derive(Debug, Error)]
+pub enum CalculatorError {
+    #[error(transparent)]
+    Projectile(#[from] ProjectileError),
+    #[error(transparent)]
+    Zero(#[from] ZeroError),
+    #[error("Line of sight angle must be between -90° and 90°, got {0}°")]
+    InvalidLineOfSightAngle(f64),
+}
@@ -0,0 +1,160 @@
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+use crate::models::TrajectoryPoint;
+use crate::calculator::physics::{TrajectoryCalculator, CalculatorError};
+
+const MAX_TRUING_ITERATIONS: usize = 20;
+const TRUING_TOLERANCE: f64 = 1e-6;  // relative change in the fitted value
+
+/// A drop measured on the range, e.g. the elevation that actually centered a group.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DropObservation {
+    pub range: f64,  // yards along the line of sight
+    pub drop: f64,   // inches relative to the line of sight, negative below
+}
+
+/// What a truing fit is allowed to adjust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TruingParameter {
+    /// The effective muzzle velocity (ft/s).
+    MuzzleVelocity,
+    /// A factor on the drag curve, equivalent to dividing the BC by it.
+    DragScale,
+}
+
+impl TruingParameter {
+    pub const ALL: [TruingParameter; 2] = [
+        TruingParameter::MuzzleVelocity,
+        TruingParameter::DragScale,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TruingParameter::MuzzleVelocity => "Muzzle Velocity",
+            TruingParameter::DragScale => "Drag Scale",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TruingResult {
+    pub parameter: TruingParameter,
+    pub value: f64,
+    pub residuals: Vec<f64>,  // inches, observed minus fitted drop per observation
+}
+
+impl TruingResult {
+    /// Root mean square of the residuals in inches.
+    pub fn rms_residual(&self) -> f64 {
+        (self.residuals.iter().map(|r| r * r).sum::<f64>() / self.residuals.len() as f64).sqrt()
+    }
+}
+
+impl TrajectoryCalculator {
+    /// Fits `parameter` so that the predicted drops best match the observations in a
+    /// least squares sense, using Gauss-Newton iterations on full trajectories. The
+    /// calculator is left trued to the fitted value, or unchanged if the fit fails.
+    pub fn true_to_observations(
+        &mut self,
+        observations: &[DropObservation],
+        parameter: TruingParameter,
+        step_size: f64,
+    ) -> Result<TruingResult, TruingError> {
+        let original = *self.truing_parameter_mut(parameter);
+        let result = self.fit(observations, parameter, step_size);
+        if result.is_err() {
+            *self.truing_parameter_mut(parameter) = original;
+        }
+        result
+    }
+
+    fn fit(
+        &mut self,
+        observations: &[DropObservation],
+        parameter: TruingParameter,
+        step_size: f64,
+    ) -> Result<TruingResult, TruingError> {
+        if observations.is_empty() {
+            return Err(TruingError::NoObservations);
+        }
+        if observations.iter().any(|o| !o.range.is_finite() || o.range <= 0.0 || !o.drop.is_finite()) {
+            return Err(TruingError::InvalidObservation);
+        }
+
+        let mut residuals = self.drop_residuals(observations, step_size)?;
+
+        for _ in 0..MAX_TRUING_ITERATIONS {
+            let value = *self.truing_parameter_mut(parameter);
+
+            // Sensitivity of each predicted drop by forward difference
+            let h = value * 1e-3;
+            *self.truing_parameter_mut(parameter) = value + h;
+            let shifted = self.drop_residuals(observations, step_size);
+            *self.truing_parameter_mut(parameter) = value;
+            let jacobian: Vec<f64> = residuals
+                .iter()
+                .zip(shifted?)
+                .map(|(r, shifted_r)| (r - shifted_r) / h)
+                .collect();
+
+            let curvature: f64 = jacobian.iter().map(|j| j * j).sum();
+            if curvature == 0.0 {
+                return Err(TruingError::Insensitive);
+            }
+            let change = residuals.iter().zip(&jacobian).map(|(r, j)| r * j).sum::<f64>() / curvature;
+
+            // Keep the parameter physical by at most halving it per iteration
+            let next = (value + change).max(0.5 * value);
+            *self.truing_parameter_mut(parameter) = next;
+            residuals = self.drop_residuals(observations, step_size)?;
+
+            if (next - value).abs() < TRUING_TOLERANCE * value {
+                return Ok(TruingResult {
+                    parameter,
+                    value: next,
+                    residuals,
+                });
+            }
+        }
+
+        Err(TruingError::NotConverged)
+    }
+
+    /// Observed minus predicted drop (inches) for each observation.
+    fn drop_residuals(&self, observations: &[DropObservation], step_size: f64) -> Result<Vec<f64>, TruingError> {
+        let max_range = observations.iter().map(|o| o.range).fold(0.0, f64::max);
+        let points = self.calculate_trajectory(max_range + 1.0, step_size)?;
+
+        observations
+            .iter()
+            .map(|o| drop_at(&points, o.range).map(|drop| o.drop - drop).ok_or(TruingError::OutOfRange))
+            .collect()
+    }
+}
+
+/// Linearly interpolated drop (inches) at `range` yards.
+fn drop_at(points: &[TrajectoryPoint], range: f64) -> Option<f64> {
+    let index = points.iter().position(|p| p.distance >= range)?;
+    if index == 0 {
+        return Some(points[0].drop);
+    }
+    let (before, after) = (&points[index - 1], &points[index]);
+    let fraction = (range - before.distance) / (after.distance - before.distance);
+    Some(before.drop + (after.drop - before.drop) * fraction)
+}
+
+#[derive(Debug, Error)]
+pub enum TruingError {
+    #[error("At least one observation is needed")]
+    NoObservations,
+    #[error("Observations need a positive range and a finite drop")]
+    InvalidObservation,
+    #[error("Projectile does not reach the observed range")]
+    OutOfRange,
+    #[error("Observed drops do not depend on the fitted parameter")]
+    Insensitive,
+    #[error("Truing did not converge")]
+    NotConverged,
+    #[error(transparent)]
+    Calculator(#[from] CalculatorError),
+}
@@ -0,0 +1,75 @@
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+use crate::models::Environment;
+use crate::calculator::physics::TrajectoryCalculator;
+
+const MAX_ZERO_ITERATIONS: usize = 50;
+const ZERO_TOLERANCE_INCHES: f64 = 0.01;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZeroSettings {
+    pub sight_height: f64,   // inches above bore
+    pub zero_range: f64,     // yards
+    pub environment: Option<Environment>,  // zero-day atmosphere, defaults to the shot's
+}
+
+impl ZeroSettings {
+    pub fn new(sight_height: f64, zero_range: f64) -> Result<Self, ZeroError> {
+        if sight_height < 0.0 || zero_range <= 0.0 {
+            return Err(ZeroError::InvalidParameters);
+        }
+
+        Ok(Self {
+            sight_height,
+            zero_range,
+            environment: None,
+        })
+    }
+
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+}
+
+impl TrajectoryCalculator {
+    /// Iteratively solves the bore elevation angle (radians) that puts the
+    /// bullet back on the line of sight at the zero range. The zero is solved in
+    /// still air, so that the day's wind shows up in full on the shot.
+    pub fn solve_zero_angle(&self, zero: &ZeroSettings, step_size: f64) -> Result<f64, ZeroError> {
+        let environment = zero.environment.as_ref().unwrap_or(self.environment()).without_wind();
+        let zero_range_feet = zero.zero_range * 3.0;
+        let mut angle = 0.0;
+
+        for _ in 0..MAX_ZERO_ITERATIONS {
+            let points = self.integrate(&environment, angle, zero.sight_height, zero_range_feet, step_size, true);
+
+            // Interpolate the height relative to the line of sight at exactly the zero range
+            let (before, after) = match points.as_slice() {
+                [.., before, after] if after.distance >= zero.zero_range => (before, after),
+                _ => return Err(ZeroError::OutOfRange),
+            };
+            let fraction = (zero.zero_range - before.distance) / (after.distance - before.distance);
+            let drop = before.drop + (after.drop - before.drop) * fraction;
+
+            if drop.abs() < ZERO_TOLERANCE_INCHES {
+                return Ok(angle);
+            }
+
+            // Small-angle correction: raise the bore by the angle subtended by the miss
+            angle -= (drop / 12.0) / zero_range_feet;
+        }
+
+        Err(ZeroError::NotConverged)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ZeroError {
+    #[error("Sight height must be non-negative and zero range must be positive")]
+    InvalidParameters,
+    #[error("Projectile does not reach the zero range")]
+    OutOfRange,
+    #[error("Zero angle did not converge")]
+    NotConverged,
+}
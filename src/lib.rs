@@ -1,7 +1,9 @@
 pub mod models;
 pub mod calculator;
+pub mod storage;
 pub mod utils;
 
 pub use models::*;
 pub use calculator::*;
+pub use storage::*;
 pub use utils::*;
\ No newline at end of file
@@ -2,61 +2,215 @@ use eframe::egui;
 use eframe::egui::ViewportBuilder;
 use ballistic_calculator::{
     Projectile,
+    BallisticCoefficient,
+    PowderSensitivity,
+    Barrel,
+    BarrelVelocityRule,
     Environment,
+    PressureReference,
+    Twist,
+    TwistDirection,
     TrajectoryCalculator,
     TrajectoryPoint,
-    G1DragModel,
-    G7DragModel,
-    DragModel,
+    StandardDragModel,
+    CustomDragModel,
+    IntegrationMethod,
+    Rifle,
+    Scope,
+    AdjustmentUnit,
+    Ammunition,
+    Profile,
+    ProfileStore,
+    ProfileFormat,
+    BulletLibrary,
+    BulletEntry,
+    DropObservation,
+    TruingParameter,
+    convert_ballistic_coefficient,
+    ballistic_coefficient_from_velocities,
+    WindProfile,
+    WindZone,
+    point_at_distance,
 };
 
+/// Spacing of the rows in the results table.
+const RESULT_INTERVAL_YARDS: f64 = 100.0;
+
+/// Parts of a loaded profile the inputs cannot show, kept when it is saved again.
+struct LoadedProfile {
+    rifle_name: String,
+    scope_name: String,
+    ammunition_name: String,
+    velocity_rule: Option<BarrelVelocityRule>,  // fitted rules only, per inch rules are edited
+    powder_table: Option<PowderSensitivity>,
+}
+
+/// Editable text for one wind zone row.
+struct WindZoneInput {
+    start: String,
+    speed: String,
+    angle: String,
+}
+
 struct BallisticCalculatorApp {
+    // Saved profiles
+    profile_name: String,
+    profile_format: ProfileFormat,
+    saved_profiles: Vec<String>,
+    selected_profile: String,
+    profile_status: Option<String>,
+
+    // Bullet library
+    bullet_library: BulletLibrary,
+    bullet_search: String,
+    bullet_library_path: String,
+    bullet_status: Option<String>,
+
     // Input states
     projectile_weight: String,
     ballistic_coefficient: String,
+    use_bc_bands: bool,
+    bc_bands: String,
     muzzle_velocity: String,
     caliber: String,
     bullet_length: String,
+    use_powder_sensitivity: bool,
+    powder_temperature: String,
+    powder_reference_temperature: String,
+    powder_fps_per_degree: String,
+    use_barrel_adjustment: bool,
+    barrel_length: String,
+    test_barrel_length: String,
+    barrel_fps_per_inch: String,
+    twist_rate: String,
+    twist_direction: TwistDirection,
     temperature: String,
     pressure: String,
+    pressure_reference: PressureReference,
     humidity: String,
     wind_speed: String,
     wind_angle: String,
+    vertical_wind: String,
+    use_wind_zones: bool,
+    wind_zones: Vec<WindZoneInput>,
     altitude: String,
     latitude: String,
+    density_altitude: String,
     range: String,
-    drag_model: DragModelType,
+    azimuth: String,
+    shooting_angle: String,
+    cant: String,
+    drag_scale: String,
+    sight_height: String,
+    zero_range: String,
+    scope_unit: AdjustmentUnit,
+    elevation_click: String,
+    windage_click: String,
+    drag_model: StandardDragModel,
+    use_custom_drag: bool,
+    custom_drag_path: String,
+    loaded_custom_drag: Option<CustomDragModel>,
+    loaded_profile: Option<LoadedProfile>,
+    integration_method: IntegrationMethod,
+    aerodynamic_jump: bool,
+    bc_convert_to: StandardDragModel,
+    bc_convert_high: String,
+    bc_convert_low: String,
+    bc_conversion: Option<String>,
+    chrono_near_velocity: String,
+    chrono_far_velocity: String,
+    chrono_distance: String,
+    truing_observations: String,
+    truing_parameter: TruingParameter,
+    truing_result: Option<String>,
     
     // Results
     calculation_results: Option<Vec<TrajectoryPoint>>,
+    calculation_scope: Option<Scope>,
     error_message: Option<String>,
-}
-
-#[derive(PartialEq)]
-enum DragModelType {
-    G1,
-    G7,
+    stability_warning: Option<String>,
 }
 
 impl Default for BallisticCalculatorApp {
     fn default() -> Self {
+        let saved_profiles = ProfileStore::open_default()
+            .and_then(|store| store.list())
+            .unwrap_or_default();
+
         Self {
+            profile_name: String::new(),
+            profile_format: ProfileFormat::Json,
+            selected_profile: saved_profiles.first().cloned().unwrap_or_default(),
+            saved_profiles,
+            profile_status: None,
+            bullet_library: BulletLibrary::builtin(),
+            bullet_search: String::new(),
+            bullet_library_path: String::new(),
+            bullet_status: None,
             projectile_weight: "168.0".to_string(),
             ballistic_coefficient: "0.223".to_string(),
+            use_bc_bands: false,
+            bc_bands: "2200:0.462, 1800:0.447, 0:0.424".to_string(),
             muzzle_velocity: "2750.0".to_string(),
             caliber: "0.308".to_string(),
             bullet_length: "1.2".to_string(),
+            use_powder_sensitivity: false,
+            powder_temperature: "59.0".to_string(),
+            powder_reference_temperature: "59.0".to_string(),
+            powder_fps_per_degree: "1.5".to_string(),
+            use_barrel_adjustment: false,
+            barrel_length: "20.0".to_string(),
+            test_barrel_length: "24.0".to_string(),
+            barrel_fps_per_inch: "25.0".to_string(),
+            twist_rate: "10.0".to_string(),
+            twist_direction: TwistDirection::Right,
             temperature: "59.0".to_string(),
             pressure: "29.92".to_string(),
+            pressure_reference: PressureReference::Station,
             humidity: "78.0".to_string(),
             wind_speed: "10.0".to_string(),
             wind_angle: "90.0".to_string(),
+            vertical_wind: "0.0".to_string(),
+            use_wind_zones: false,
+            wind_zones: vec![
+                WindZoneInput { start: "0".to_string(), speed: "10.0".to_string(), angle: "90.0".to_string() },
+                WindZoneInput { start: "300".to_string(), speed: "12.0".to_string(), angle: "90.0".to_string() },
+                WindZoneInput { start: "700".to_string(), speed: "8.0".to_string(), angle: "60.0".to_string() },
+            ],
             altitude: "1000.0".to_string(),
             latitude: "45.0".to_string(),
+            density_altitude: "0.0".to_string(),
             range: "1000.0".to_string(),
-            drag_model: DragModelType::G1,
+            azimuth: "0.0".to_string(),
+            shooting_angle: "0.0".to_string(),
+            cant: "0.0".to_string(),
+            drag_scale: "1.0".to_string(),
+            sight_height: "1.5".to_string(),
+            zero_range: "100.0".to_string(),
+            scope_unit: AdjustmentUnit::Moa,
+            elevation_click: "0.25".to_string(),
+            windage_click: "0.25".to_string(),
+            drag_model: StandardDragModel::G1,
+            use_custom_drag: false,
+            custom_drag_path: String::new(),
+            loaded_custom_drag: None,
+            loaded_profile: None,
+            integration_method: IntegrationMethod::RungeKutta4,
+            aerodynamic_jump: true,
+            bc_convert_to: StandardDragModel::G7,
+            bc_convert_high: "2700.0".to_string(),
+            bc_convert_low: "1400.0".to_string(),
+            bc_conversion: None,
+            chrono_near_velocity: "2750.0".to_string(),
+            chrono_far_velocity: "2350.0".to_string(),
+            chrono_distance: "300.0".to_string(),
+            truing_observations: "600:-104.8, 800:-231.4".to_string(),
+            truing_parameter: TruingParameter::MuzzleVelocity,
+            truing_result: None,
             calculation_results: None,
+            calculation_scope: None,
             error_message: None,
+            stability_warning: None,
         }
     }
 }
@@ -68,6 +222,74 @@ impl eframe::App for BallisticCalculatorApp {
                 // Left panel - Inputs
                 ui.vertical(|ui| {
                     ui.heading("Inputs");
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.heading("Profile");
+                            ui.horizontal(|ui| {
+                                ui.label("Name:");
+                                ui.text_edit_singleline(&mut self.profile_name);
+                                for format in ProfileFormat::ALL {
+                                    ui.radio_value(&mut self.profile_format, format, format.name());
+                                }
+                                if ui.button("Save Profile").clicked() {
+                                    self.save_profile();
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                egui::ComboBox::from_id_source("saved_profiles")
+                                    .selected_text(self.selected_profile.as_str())
+                                    .show_ui(ui, |ui| {
+                                        for name in &self.saved_profiles {
+                                            ui.selectable_value(&mut self.selected_profile, name.clone(), name.as_str());
+                                        }
+                                    });
+                                if ui.button("Load Profile").clicked() {
+                                    self.load_profile();
+                                }
+                            });
+                            if let Some(status) = &self.profile_status {
+                                ui.label(status);
+                            }
+                        });
+                    });
+
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.heading("Bullet Library");
+                            ui.horizontal(|ui| {
+                                ui.label("Search:");
+                                ui.text_edit_singleline(&mut self.bullet_search);
+                            });
+                            let mut chosen = None;
+                            egui::ScrollArea::vertical()
+                                .id_source("bullet_library")
+                                .max_height(120.0)
+                                .show(ui, |ui| {
+                                    for entry in self.bullet_library.search(&self.bullet_search) {
+                                        if ui.selectable_label(false, entry.name()).clicked() {
+                                            chosen = Some(entry.clone());
+                                        }
+                                    }
+                                });
+                            if let Some(entry) = chosen {
+                                self.apply_bullet(&entry);
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label("Extension file:");
+                                ui.text_edit_singleline(&mut self.bullet_library_path);
+                                if ui.button("Merge").clicked() {
+                                    self.bullet_status = Some(match self.bullet_library.merge_file(&self.bullet_library_path) {
+                                        Ok(count) => format!("Merged {} bullets", count),
+                                        Err(e) => e.to_string(),
+                                    });
+                                }
+                            });
+                            if let Some(status) = &self.bullet_status {
+                                ui.label(status);
+                            }
+                        });
+                    });
+
                     ui.group(|ui| {
                         ui.vertical(|ui| {
                             ui.heading("Projectile Data");
@@ -80,8 +302,15 @@ impl eframe::App for BallisticCalculatorApp {
                                 ui.text_edit_singleline(&mut self.ballistic_coefficient);
                             });
                             ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.use_bc_bands, "BC bands (fps:BC, ...):");
+                                ui.text_edit_singleline(&mut self.bc_bands);
+                            });
+                            ui.horizontal(|ui| {
+                                // A profile's temperature table sets the velocity instead
+                                let powder_table = self.use_powder_sensitivity
+                                    && self.loaded_profile.as_ref().is_some_and(|loaded| loaded.powder_table.is_some());
                                 ui.label("Muzzle Velocity (fps):");
-                                ui.text_edit_singleline(&mut self.muzzle_velocity);
+                                ui.add_enabled(!powder_table, egui::TextEdit::singleline(&mut self.muzzle_velocity));
                             });
                             ui.horizontal(|ui| {
                                 ui.label("Caliber (inches):");
@@ -91,6 +320,65 @@ impl eframe::App for BallisticCalculatorApp {
                                 ui.label("Bullet Length (inches):");
                                 ui.text_edit_singleline(&mut self.bullet_length);
                             });
+                            ui.checkbox(&mut self.use_powder_sensitivity, "Powder temperature sensitivity");
+                            if self.use_powder_sensitivity {
+                                ui.horizontal(|ui| {
+                                    ui.label("Powder Temperature (°F):");
+                                    ui.text_edit_singleline(&mut self.powder_temperature);
+                                });
+                                let powder_table = self.loaded_profile.as_mut().filter(|loaded| loaded.powder_table.is_some());
+                                if let Some(loaded) = powder_table {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Using the profile's temperature table");
+                                        if ui.button("Use Linear").clicked() {
+                                            loaded.powder_table = None;
+                                        }
+                                    });
+                                } else {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Velocity Reference Temperature (°F):");
+                                        ui.text_edit_singleline(&mut self.powder_reference_temperature);
+                                    });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Sensitivity (fps/°F):");
+                                        ui.text_edit_singleline(&mut self.powder_fps_per_degree);
+                                    });
+                                }
+                            }
+                            ui.checkbox(&mut self.use_barrel_adjustment, "Barrel length adjustment");
+                            if self.use_barrel_adjustment {
+                                ui.horizontal(|ui| {
+                                    ui.label("Barrel Length (inches):");
+                                    ui.text_edit_singleline(&mut self.barrel_length);
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Test Barrel Length (inches):");
+                                    ui.text_edit_singleline(&mut self.test_barrel_length);
+                                });
+                                let velocity_rule = self.loaded_profile.as_mut().filter(|loaded| loaded.velocity_rule.is_some());
+                                if let Some(loaded) = velocity_rule {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Using the profile's fitted velocity curve");
+                                        if ui.button("Use fps/inch").clicked() {
+                                            loaded.velocity_rule = None;
+                                        }
+                                    });
+                                } else {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Velocity Change (fps/inch):");
+                                        ui.text_edit_singleline(&mut self.barrel_fps_per_inch);
+                                    });
+                                }
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label("Twist Rate (in/turn):");
+                                ui.text_edit_singleline(&mut self.twist_rate);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Twist Direction:");
+                                ui.radio_value(&mut self.twist_direction, TwistDirection::Right, "Right");
+                                ui.radio_value(&mut self.twist_direction, TwistDirection::Left, "Left");
+                            });
                         });
                     });
 
@@ -105,6 +393,11 @@ impl eframe::App for BallisticCalculatorApp {
                                 ui.label("Pressure (inHg):");
                                 ui.text_edit_singleline(&mut self.pressure);
                             });
+                            ui.horizontal(|ui| {
+                                ui.label("Pressure Type:");
+                                ui.radio_value(&mut self.pressure_reference, PressureReference::Station, "Station");
+                                ui.radio_value(&mut self.pressure_reference, PressureReference::Barometric, "Barometric");
+                            });
                             ui.horizontal(|ui| {
                                 ui.label("Humidity (%):");
                                 ui.text_edit_singleline(&mut self.humidity);
@@ -117,6 +410,34 @@ impl eframe::App for BallisticCalculatorApp {
                                 ui.label("Wind Angle (deg):");
                                 ui.text_edit_singleline(&mut self.wind_angle);
                             });
+                            ui.horizontal(|ui| {
+                                ui.label("Vertical Wind (mph, + up):");
+                                ui.text_edit_singleline(&mut self.vertical_wind);
+                            });
+                            ui.checkbox(&mut self.use_wind_zones, "Wind zones (start yd, mph, deg):");
+                            if self.use_wind_zones {
+                                let mut removed = None;
+                                for (index, zone) in self.wind_zones.iter_mut().enumerate() {
+                                    ui.horizontal(|ui| {
+                                        ui.add(egui::TextEdit::singleline(&mut zone.start).desired_width(60.0));
+                                        ui.add(egui::TextEdit::singleline(&mut zone.speed).desired_width(60.0));
+                                        ui.add(egui::TextEdit::singleline(&mut zone.angle).desired_width(60.0));
+                                        if ui.button("Remove").clicked() {
+                                            removed = Some(index);
+                                        }
+                                    });
+                                }
+                                if let Some(index) = removed {
+                                    self.wind_zones.remove(index);
+                                }
+                                if ui.button("Add Zone").clicked() {
+                                    self.wind_zones.push(WindZoneInput {
+                                        start: String::new(),
+                                        speed: self.wind_speed.clone(),
+                                        angle: self.wind_angle.clone(),
+                                    });
+                                }
+                            }
                             ui.horizontal(|ui| {
                                 ui.label("Altitude (ft):");
                                 ui.text_edit_singleline(&mut self.altitude);
@@ -125,6 +446,26 @@ impl eframe::App for BallisticCalculatorApp {
                                 ui.label("Latitude (deg):");
                                 ui.text_edit_singleline(&mut self.latitude);
                             });
+                            ui.horizontal(|ui| {
+                                ui.label("Standard Atmosphere:");
+                                let altitude = self.altitude.parse::<f64>().unwrap_or(0.0);
+                                if ui.button("ICAO").clicked() {
+                                    self.apply_atmosphere(&Environment::icao_standard(altitude));
+                                }
+                                if ui.button("Army Std Metro").clicked() {
+                                    self.apply_atmosphere(&Environment::army_standard_metro(altitude));
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Density Altitude (ft):");
+                                ui.text_edit_singleline(&mut self.density_altitude);
+                                if ui.button("Apply").clicked() {
+                                    match self.density_altitude.parse::<f64>() {
+                                        Ok(da) => self.apply_atmosphere(&Environment::from_density_altitude(da)),
+                                        Err(_) => self.error_message = Some("Invalid density altitude value".to_string()),
+                                    }
+                                }
+                            });
                         });
                     });
 
@@ -135,11 +476,109 @@ impl eframe::App for BallisticCalculatorApp {
                                 ui.label("Range (yards):");
                                 ui.text_edit_singleline(&mut self.range);
                             });
+                            ui.horizontal(|ui| {
+                                ui.label("Azimuth of Fire (deg):");
+                                ui.text_edit_singleline(&mut self.azimuth);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Shooting Angle (deg, + uphill):");
+                                ui.text_edit_singleline(&mut self.shooting_angle);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Cant (deg, + clockwise):");
+                                ui.text_edit_singleline(&mut self.cant);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Drag Scale:");
+                                ui.text_edit_singleline(&mut self.drag_scale);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Sight Height (inches):");
+                                ui.text_edit_singleline(&mut self.sight_height);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Zero Range (yards):");
+                                ui.text_edit_singleline(&mut self.zero_range);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Scope Click (elevation, windage):");
+                                ui.add(egui::TextEdit::singleline(&mut self.elevation_click).desired_width(60.0));
+                                ui.add(egui::TextEdit::singleline(&mut self.windage_click).desired_width(60.0));
+                                ui.radio_value(&mut self.scope_unit, AdjustmentUnit::Moa, AdjustmentUnit::Moa.name());
+                                ui.radio_value(&mut self.scope_unit, AdjustmentUnit::Mil, AdjustmentUnit::Mil.name());
+                            });
                             ui.horizontal(|ui| {
                                 ui.label("Drag Model:");
-                                ui.radio_value(&mut self.drag_model, DragModelType::G1, "G1");
-                                ui.radio_value(&mut self.drag_model, DragModelType::G7, "G7");
+                                for model in StandardDragModel::ALL {
+                                    ui.radio_value(&mut self.drag_model, model, model.name());
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.use_custom_drag, "Custom drag file:");
+                                ui.text_edit_singleline(&mut self.custom_drag_path);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Integrator:");
+                                for method in IntegrationMethod::ALL {
+                                    ui.radio_value(&mut self.integration_method, method, method.name());
+                                }
+                            });
+                            ui.checkbox(&mut self.aerodynamic_jump, "Aerodynamic Jump");
+                        });
+                    });
+
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.heading("BC Conversion");
+                            ui.horizontal(|ui| {
+                                ui.label(format!("Convert {} BC to:", self.drag_model.name()));
+                                for model in StandardDragModel::ALL {
+                                    ui.radio_value(&mut self.bc_convert_to, model, model.name());
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Velocity Range (fps):");
+                                ui.add(egui::TextEdit::singleline(&mut self.bc_convert_high).desired_width(60.0));
+                                ui.label("to");
+                                ui.add(egui::TextEdit::singleline(&mut self.bc_convert_low).desired_width(60.0));
+                                if ui.button("Convert").clicked() {
+                                    self.convert_ballistic_coefficient();
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Chronograph (fps, fps, yd apart):");
+                                ui.add(egui::TextEdit::singleline(&mut self.chrono_near_velocity).desired_width(60.0));
+                                ui.add(egui::TextEdit::singleline(&mut self.chrono_far_velocity).desired_width(60.0));
+                                ui.add(egui::TextEdit::singleline(&mut self.chrono_distance).desired_width(60.0));
+                                if ui.button("Solve BC").clicked() {
+                                    self.solve_chronograph_bc();
+                                }
+                            });
+                            if let Some(conversion) = &self.bc_conversion {
+                                ui.label(conversion);
+                            }
+                        });
+                    });
+
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.heading("Truing");
+                            ui.horizontal(|ui| {
+                                ui.label("Observed drops (yd:in, ...):");
+                                ui.text_edit_singleline(&mut self.truing_observations);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Fit:");
+                                for parameter in TruingParameter::ALL {
+                                    ui.radio_value(&mut self.truing_parameter, parameter, parameter.name());
+                                }
+                                if ui.button("True").clicked() {
+                                    self.true_calculator();
+                                }
                             });
+                            if let Some(result) = &self.truing_result {
+                                ui.label(result);
+                            }
                         });
                     });
 
@@ -150,6 +589,10 @@ impl eframe::App for BallisticCalculatorApp {
                     if let Some(error) = &self.error_message {
                         ui.colored_label(egui::Color32::RED, error);
                     }
+
+                    if let Some(warning) = &self.stability_warning {
+                        ui.colored_label(egui::Color32::YELLOW, warning);
+                    }
                 });
 
                 // Right panel - Results
@@ -158,14 +601,46 @@ impl eframe::App for BallisticCalculatorApp {
                     if let Some(results) = &self.calculation_results {
                         egui::ScrollArea::vertical().show(ui, |ui| {
                             ui.group(|ui| {
-                                for point in results.iter().step_by(100) {
+                                let rows = (1..)
+                                    .map(|row| row as f64 * RESULT_INTERVAL_YARDS)
+                                    .map_while(|distance| point_at_distance(results, distance));
+                                for point in rows {
                                     ui.label(format!(
-                                        "Distance: {:.1} yards\nDrop: {:.1} inches\nWindage: {:.1} inches\nVelocity: {:.0} fps\nTime: {:.3} sec\n",
+                                        "Distance: {:.1} yards (horizontal {:.1} yards)\nDrop: {:.1} inches\nWindage: {:.1} inches\nVelocity: {:.0} fps (Mach {:.2})\nTime: {:.3} sec\nCoriolis: {:.2} in vertical, {:.2} in horizontal\nSpin Drift: {:.2} in\nAerodynamic Jump: {:.2} in\nVertical Wind: {:.2} in (estimate)\n",
                                         point.distance,
+                                        point.horizontal_distance,
                                         point.drop,
                                         point.windage,
                                         point.velocity,
-                                        point.time
+                                        point.mach,
+                                        point.time,
+                                        point.coriolis_vertical,
+                                        point.coriolis_horizontal,
+                                        point.spin_drift,
+                                        point.aerodynamic_jump,
+                                        point.vertical_wind
+                                    ));
+                                    if let Some(scope) = &self.calculation_scope {
+                                        let (elevation, windage) = scope.clicks(&point);
+                                        ui.label(format!(
+                                            "Dial: {:.0} clicks {}, {:.0} clicks {} ({})",
+                                            elevation.abs(),
+                                            if elevation >= 0.0 { "up" } else { "down" },
+                                            windage.abs(),
+                                            if windage >= 0.0 { "right" } else { "left" },
+                                            scope.unit.name()
+                                        ));
+                                    }
+                                    let (cant_2_vertical, cant_2_horizontal) = point.cant_shift(2.0);
+                                    let (cant_5_vertical, cant_5_horizontal) = point.cant_shift(5.0);
+                                    ui.label(format!(
+                                        "Cant: {:.2} in vertical, {:.2} in horizontal\n2° cant costs {:.2} in vertical, {:.2} in horizontal\n5° cant costs {:.2} in vertical, {:.2} in horizontal\n",
+                                        point.cant_vertical,
+                                        point.cant_horizontal,
+                                        cant_2_vertical,
+                                        cant_2_horizontal,
+                                        cant_5_vertical,
+                                        cant_5_horizontal
                                     ));
                                     ui.separator();
                                 }
@@ -179,65 +654,425 @@ impl eframe::App for BallisticCalculatorApp {
 }
 
 impl BallisticCalculatorApp {
-    fn calculate_trajectory(&mut self) {
-        self.error_message = None;
-        
-        let parse_input = |s: &str, field: &str| -> Result<f64, String> {
-            s.parse::<f64>().map_err(|_| format!("Invalid {} value", field))
-        };
+    fn apply_atmosphere(&mut self, environment: &Environment) {
+        self.temperature = format!("{:.1}", environment.temperature);
+        self.pressure = format!("{:.2}", environment.station_pressure());
+        self.pressure_reference = PressureReference::Station;
+        self.humidity = format!("{:.1}", environment.humidity);
+        self.altitude = format!("{:.0}", environment.altitude);
+        self.density_altitude = format!("{:.0}", environment.density_altitude());
+    }
 
-        let result = (|| -> Result<Vec<TrajectoryPoint>, String> {
-            // Parse projectile data
-            let projectile_weight = parse_input(&self.projectile_weight, "projectile weight")?;
+    fn convert_ballistic_coefficient(&mut self) {
+        let result = (|| -> Result<String, String> {
             let ballistic_coefficient = parse_input(&self.ballistic_coefficient, "ballistic coefficient")?;
-            let muzzle_velocity = parse_input(&self.muzzle_velocity, "muzzle velocity")?;
+            let high = parse_input(&self.bc_convert_high, "velocity range")?;
+            let low = parse_input(&self.bc_convert_low, "velocity range")?;
+            let converted = convert_ballistic_coefficient(
+                ballistic_coefficient,
+                self.drag_model,
+                self.bc_convert_to,
+                (high, low),
+            ).map_err(|e| e.to_string())?;
+
+            let weight = parse_input(&self.projectile_weight, "projectile weight")?;
             let caliber = parse_input(&self.caliber, "caliber")?;
-            let bullet_length = parse_input(&self.bullet_length, "bullet length")?;
-            
-            let projectile = Projectile::new(
+            let sectional_density = weight / (7000.0 * caliber * caliber);
+            Ok(format!(
+                "{} BC: {:.3}\nForm factor: {:.3} ({}), {:.3} ({})",
+                self.bc_convert_to.name(),
+                converted,
+                sectional_density / ballistic_coefficient,
+                self.drag_model.name(),
+                sectional_density / converted,
+                self.bc_convert_to.name()
+            ))
+        })();
+
+        self.bc_conversion = Some(result.unwrap_or_else(|e| e));
+    }
+
+    /// Builds the rifle, scope and load described by the current inputs.
+    fn build_profile(&self) -> Result<Profile, String> {
+        // Parse projectile data
+        let projectile_weight = parse_input(&self.projectile_weight, "projectile weight")?;
+        // A bullet-specific drag curve needs no BC, so the field may be left blank
+        let ballistic_coefficient = if self.use_custom_drag {
+            None
+        } else if self.use_bc_bands {
+            let bands = self.bc_bands
+                .split(',')
+                .map(|band| {
+                    let (velocity, bc) = band.split_once(':').ok_or("Invalid BC band, expected fps:BC")?;
+                    Ok((parse_input(velocity.trim(), "BC band velocity")?, parse_input(bc.trim(), "BC band value")?))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Some(BallisticCoefficient::banded(bands).map_err(|e| e.to_string())?)
+        } else {
+            Some(parse_input(&self.ballistic_coefficient, "ballistic coefficient")?.into())
+        };
+        let muzzle_velocity = parse_input(&self.muzzle_velocity, "muzzle velocity")?;
+        let caliber = parse_input(&self.caliber, "caliber")?;
+        let bullet_length = parse_input(&self.bullet_length, "bullet length")?;
+        
+        let projectile = match ballistic_coefficient {
+            Some(ballistic_coefficient) => Projectile::new(
                 projectile_weight,
                 ballistic_coefficient,
                 muzzle_velocity,
                 caliber,
                 bullet_length,
-            ).map_err(|e| e.to_string())?;
+            ),
+            None => Projectile::without_ballistic_coefficient(projectile_weight, muzzle_velocity, caliber, bullet_length),
+        }.map_err(|e| e.to_string())?;
 
-            // Parse environmental data
-            let temperature = parse_input(&self.temperature, "temperature")?;
-            let pressure = parse_input(&self.pressure, "pressure")?;
-            let humidity = parse_input(&self.humidity, "humidity")?;
-            let wind_speed = parse_input(&self.wind_speed, "wind speed")?;
-            let wind_angle = parse_input(&self.wind_angle, "wind angle")?;
-            let altitude = parse_input(&self.altitude, "altitude")?;
-            let latitude = parse_input(&self.latitude, "latitude")?;
-            
-            let environment = Environment::new(
-                temperature,
-                pressure,
-                humidity,
-                wind_speed,
-                wind_angle,
-                altitude,
-                latitude,
-            ).map_err(|e| e.to_string())?;
+        // Names the inputs cannot edit come from the loaded profile
+        let name = self.profile_name.trim();
+        let (rifle_name, scope_name, ammunition_name) = match &self.loaded_profile {
+            Some(loaded) => (loaded.rifle_name.as_str(), loaded.scope_name.as_str(), loaded.ammunition_name.as_str()),
+            None => (name, name, name),
+        };
 
-            // Create drag model
-            let drag_model: Box<dyn DragModel> = match self.drag_model {
-                DragModelType::G1 => Box::new(G1DragModel),
-                DragModelType::G7 => Box::new(G7DragModel),
+        // Parse ammunition data
+        let mut ammunition = Ammunition::new(ammunition_name, projectile, self.drag_model);
+        if self.use_custom_drag {
+            // A curve loaded with a profile is kept until a file is chosen instead
+            let custom_drag = match &self.loaded_custom_drag {
+                Some(custom_drag) if self.custom_drag_path.is_empty() => custom_drag.clone(),
+                _ => CustomDragModel::from_file(&self.custom_drag_path).map_err(|e| e.to_string())?,
+            };
+            ammunition = ammunition.with_custom_drag(custom_drag);
+        }
+        if self.use_powder_sensitivity {
+            let powder_table = self.loaded_profile.as_ref().and_then(|loaded| loaded.powder_table.clone());
+            let sensitivity = match powder_table {
+                Some(powder_table) => powder_table,
+                None => PowderSensitivity::linear(
+                    parse_input(&self.powder_reference_temperature, "powder reference temperature")?,
+                    muzzle_velocity,
+                    parse_input(&self.powder_fps_per_degree, "powder sensitivity")?,
+                ).map_err(|e| e.to_string())?,
             };
+            ammunition = ammunition.with_powder_sensitivity(sensitivity);
+        }
+        if self.use_barrel_adjustment {
+            ammunition = ammunition.with_test_barrel_length(parse_input(&self.test_barrel_length, "test barrel length")?);
+        }
+
+        // Parse rifle data
+        let velocity_rule = match self.loaded_profile.as_ref().and_then(|loaded| loaded.velocity_rule) {
+            Some(velocity_rule) => velocity_rule,
+            None => BarrelVelocityRule::PerInch(parse_input(&self.barrel_fps_per_inch, "velocity change per inch")?),
+        };
+        let barrel = Barrel::new(parse_input(&self.barrel_length, "barrel length")?, velocity_rule)
+            .map_err(|e| e.to_string())?;
+        let twist_rate = parse_input(&self.twist_rate, "twist rate")?;
+        let twist = Twist::new(twist_rate, self.twist_direction).map_err(|e| e.to_string())?;
+        let rifle = Rifle::new(rifle_name, barrel, twist);
+
+        // Parse scope data
+        let scope = Scope::new(
+            scope_name,
+            parse_input(&self.sight_height, "sight height")?,
+            parse_input(&self.zero_range, "zero range")?,
+            self.scope_unit,
+            parse_input(&self.elevation_click, "elevation click value")?,
+            parse_input(&self.windage_click, "windage click value")?,
+        ).map_err(|e| e.to_string())?;
+
+        Ok(Profile::new(name, rifle, scope, ammunition))
+    }
+
+    /// Fills the projectile inputs from a library bullet, switching to a drag model
+    /// the maker publishes a BC for when the selected one has none.
+    fn apply_bullet(&mut self, entry: &BulletEntry) {
+        if entry.ballistic_coefficient(self.drag_model).is_none() {
+            if let Some(model) = [StandardDragModel::G7, StandardDragModel::G1]
+                .into_iter()
+                .find(|&model| entry.ballistic_coefficient(model).is_some())
+            {
+                self.drag_model = model;
+            }
+        }
+
+        self.projectile_weight = entry.weight_grains.to_string();
+        self.caliber = entry.caliber.to_string();
+        self.bullet_length = entry.length.to_string();
+        if let Some(bc) = entry.ballistic_coefficient(self.drag_model) {
+            self.ballistic_coefficient = bc.to_string();
+        }
+
+        // The loaded profile's temperature table and load name belonged to its own bullet
+        if let Some(loaded) = &mut self.loaded_profile {
+            loaded.powder_table = None;
+            loaded.ammunition_name = entry.name();
+        }
+        self.use_bc_bands = false;
+        self.use_custom_drag = false;
+        self.bullet_status = Some(format!("Using {} ({} BC)", entry.name(), self.drag_model.name()));
+    }
+
+    fn save_profile(&mut self) {
+        let result = (|| -> Result<String, String> {
+            let profile = self.build_profile()?;
+            let store = ProfileStore::open_default().map_err(|e| e.to_string())?;
+            let path = store.save(&profile, self.profile_format).map_err(|e| e.to_string())?;
+            self.saved_profiles = store.list().map_err(|e| e.to_string())?;
+            if let Some(stem) = path.file_stem() {
+                self.selected_profile = stem.to_string_lossy().into_owned();
+            }
+            Ok(format!("Saved {}", path.display()))
+        })();
+
+        self.profile_status = Some(result.unwrap_or_else(|e| e));
+    }
+
+    fn load_profile(&mut self) {
+        let result = ProfileStore::open_default()
+            .and_then(|store| store.load(&self.selected_profile))
+            .map_err(|e| e.to_string());
+
+        self.profile_status = Some(match result {
+            Ok(profile) => {
+                self.apply_profile(&profile);
+                format!("Loaded {}", profile.name)
+            }
+            Err(e) => e,
+        });
+    }
+
+    /// Fills the rifle, scope and load inputs from a saved profile.
+    fn apply_profile(&mut self, profile: &Profile) {
+        let projectile = &profile.ammunition.projectile;
+        self.profile_name = profile.name.clone();
 
-            // Create calculator and compute trajectory
-            let calculator = TrajectoryCalculator::new(
-                projectile,
-                environment,
-                drag_model
-            );
+        self.projectile_weight = projectile.weight_grains.to_string();
+        match &projectile.ballistic_coefficient {
+            Some(BallisticCoefficient::Single(bc)) => {
+                self.ballistic_coefficient = bc.to_string();
+                self.use_bc_bands = false;
+            }
+            Some(BallisticCoefficient::Banded(bands)) => {
+                let bands: Vec<String> = bands.iter().map(|(velocity, bc)| format!("{}:{}", velocity, bc)).collect();
+                self.bc_bands = bands.join(", ");
+                self.use_bc_bands = true;
+            }
+            None => {
+                self.ballistic_coefficient.clear();
+                self.use_bc_bands = false;
+            }
+        }
+        self.muzzle_velocity = projectile.muzzle_velocity.to_string();
+        self.caliber = projectile.caliber.to_string();
+        self.bullet_length = projectile.length.to_string();
+
+        self.drag_model = profile.ammunition.drag_model;
+        self.loaded_custom_drag = profile.ammunition.custom_drag.clone();
+        self.use_custom_drag = self.loaded_custom_drag.is_some();
+        self.custom_drag_path.clear();
+
+        // The GUI edits the linear sensitivity around the muzzle velocity input; a table is kept as is
+        self.use_powder_sensitivity = profile.ammunition.powder_sensitivity.is_some();
+        if let Some(PowderSensitivity::Linear { reference_temperature, reference_velocity, fps_per_degree }) =
+            profile.ammunition.powder_sensitivity
+        {
+            self.powder_reference_temperature = reference_temperature.to_string();
+            self.powder_fps_per_degree = fps_per_degree.to_string();
+            self.muzzle_velocity = reference_velocity.to_string();
+        }
+        let powder_table = profile.ammunition.powder_sensitivity
+            .clone()
+            .filter(|sensitivity| matches!(sensitivity, PowderSensitivity::Table(_)));
+
+        self.use_barrel_adjustment = profile.ammunition.test_barrel_length.is_some();
+        if let Some(test_barrel_length) = profile.ammunition.test_barrel_length {
+            self.test_barrel_length = test_barrel_length.to_string();
+        }
+        self.barrel_length = profile.rifle.barrel.length.to_string();
+        let velocity_rule = match profile.rifle.barrel.velocity_rule {
+            BarrelVelocityRule::PerInch(fps_per_inch) => {
+                self.barrel_fps_per_inch = fps_per_inch.to_string();
+                None
+            }
+            fitted => Some(fitted),
+        };
+        self.twist_rate = profile.rifle.twist.rate.to_string();
+        self.twist_direction = profile.rifle.twist.direction;
+
+        self.sight_height = profile.scope.sight_height.to_string();
+        self.zero_range = profile.scope.zero_range.to_string();
+        self.scope_unit = profile.scope.unit;
+        self.elevation_click = profile.scope.elevation_click.to_string();
+        self.windage_click = profile.scope.windage_click.to_string();
+
+        self.loaded_profile = Some(LoadedProfile {
+            rifle_name: profile.rifle.name.clone(),
+            scope_name: profile.scope.name.clone(),
+            ammunition_name: profile.ammunition.name.clone(),
+            velocity_rule,
+            powder_table,
+        });
+    }
+
+    /// Builds the day's conditions from the current inputs.
+    fn build_environment(&mut self) -> Result<Environment, String> {
+        // Parse environmental data
+        let temperature = parse_input(&self.temperature, "temperature")?;
+        let pressure = parse_input(&self.pressure, "pressure")?;
+        let humidity = parse_input(&self.humidity, "humidity")?;
+        let wind_speed = parse_input(&self.wind_speed, "wind speed")?;
+        let wind_angle = parse_input(&self.wind_angle, "wind angle")?;
+        let vertical_wind = parse_input(&self.vertical_wind, "vertical wind")?;
+        let altitude = parse_input(&self.altitude, "altitude")?;
+        let latitude = parse_input(&self.latitude, "latitude")?;
+        
+        let mut environment = Environment::new(
+            temperature,
+            pressure,
+            humidity,
+            wind_speed,
+            wind_angle,
+            altitude,
+            latitude,
+        )
+        .map_err(|e| e.to_string())?
+        .with_pressure_reference(self.pressure_reference)
+        .with_vertical_wind(vertical_wind);
+
+        if self.use_powder_sensitivity {
+            environment = environment.with_powder_temperature(parse_input(&self.powder_temperature, "powder temperature")?);
+        }
+
+        if self.use_wind_zones {
+            let zones = self.wind_zones
+                .iter()
+                .map(|zone| {
+                    WindZone::new(
+                        parse_input(&zone.start, "wind zone start")?,
+                        parse_input(&zone.speed, "wind zone speed")?,
+                        parse_input(&zone.angle, "wind zone angle")?,
+                    )
+                    .map_err(|e| e.to_string())
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            environment = environment.with_wind_profile(WindProfile::new(zones).map_err(|e| e.to_string())?);
+        }
+        self.density_altitude = format!("{:.0}", environment.density_altitude());
+
+        Ok(environment)
+    }
+
+    /// Builds a calculator from the current inputs.
+    fn build_calculator(&mut self) -> Result<TrajectoryCalculator, String> {
+        let profile = self.build_profile()?;
+        let environment = self.build_environment()?;
+
+        let azimuth = parse_input(&self.azimuth, "azimuth")?;
+        let shooting_angle = parse_input(&self.shooting_angle, "shooting angle")?;
+        let cant = parse_input(&self.cant, "cant")?;
+        let drag_scale = parse_input(&self.drag_scale, "drag scale")?;
 
-            let range = parse_input(&self.range, "range")?;
-            Ok(calculator.calculate_trajectory(range, 0.01))
+        // Create calculator from the profile with this shot's settings
+        let calculator = TrajectoryCalculator::from_profile(&profile, environment)
+            .map_err(|e| e.to_string())?
+            .with_integrator(self.integration_method.to_integrator())
+            .with_azimuth(azimuth)
+            .with_line_of_sight_angle(shooting_angle)
+            .map_err(|e| e.to_string())?
+            .with_cant(cant)
+            .with_aerodynamic_jump(self.aerodynamic_jump)
+            .with_drag_scale(drag_scale);
+
+        self.stability_warning = calculator.stability_warning().map(|w| w.to_string());
+        self.calculation_scope = Some(profile.scope);
+
+        Ok(calculator)
+    }
+
+    /// Solves the BC for the selected drag model from two chronograph readings in the
+    /// current atmosphere and writes it to the BC input.
+    fn solve_chronograph_bc(&mut self) {
+        let result = (|| -> Result<String, String> {
+            let near_velocity = parse_input(&self.chrono_near_velocity, "near velocity")?;
+            let far_velocity = parse_input(&self.chrono_far_velocity, "far velocity")?;
+            let distance = parse_input(&self.chrono_distance, "chronograph distance")?;
+            let calculator = self.build_calculator()?;
+
+            let ballistic_coefficient = ballistic_coefficient_from_velocities(
+                near_velocity,
+                far_velocity,
+                distance,
+                calculator.environment(),
+                self.drag_model.to_model().as_ref(),
+            ).map_err(|e| e.to_string())?;
+
+            self.ballistic_coefficient = format!("{:.3}", ballistic_coefficient);
+            Ok(format!("{} BC from chronograph: {:.3}", self.drag_model.name(), ballistic_coefficient))
+        })();
+
+        self.bc_conversion = Some(result.unwrap_or_else(|e| e));
+    }
+
+    /// Fits the chosen parameter to the observed drops and writes it back to its input.
+    fn true_calculator(&mut self) {
+        let result = (|| -> Result<String, String> {
+            let observations = self.truing_observations
+                .split(',')
+                .map(|observation| {
+                    let (range, drop) = observation.split_once(':').ok_or("Invalid observation, expected yd:in")?;
+                    let parse = |s: &str| s.trim().parse::<f64>().map_err(|_| format!("Invalid observation {}", observation.trim()));
+                    Ok(DropObservation { range: parse(range)?, drop: parse(drop)? })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            let mut calculator = self.build_calculator()?;
+            let built_velocity = calculator.projectile().muzzle_velocity;
+            let fit = calculator
+                .true_to_observations(&observations, self.truing_parameter, 0.01)
+                .map_err(|e| e.to_string())?;
+
+            match fit.parameter {
+                TruingParameter::MuzzleVelocity => {
+                    // Shift the input by the fitted change, since it may hold the velocity
+                    // at the reference powder temperature rather than the effective one.
+                    // A temperature table sets the velocity instead, so the whole table shifts.
+                    let change = fit.value - built_velocity;
+                    let powder_table = self.loaded_profile
+                        .as_mut()
+                        .filter(|_| self.use_powder_sensitivity)
+                        .and_then(|loaded| loaded.powder_table.as_mut());
+                    match powder_table {
+                        Some(powder_table) => *powder_table = powder_table.shifted(change).map_err(|e| e.to_string())?,
+                        None => {
+                            let input_velocity = self.muzzle_velocity.parse::<f64>().unwrap_or(built_velocity);
+                            self.muzzle_velocity = format!("{:.1}", input_velocity + change);
+                        }
+                    }
+                }
+                TruingParameter::DragScale => self.drag_scale = format!("{:.4}", fit.value),
+            }
+            let residuals: Vec<String> = fit.residuals.iter().map(|r| format!("{:.2}", r)).collect();
+            Ok(format!(
+                "{}: {:.4}\nResiduals (in): {}\nRMS: {:.2} in",
+                fit.parameter.name(),
+                fit.value,
+                residuals.join(", "),
+                fit.rms_residual()
+            ))
         })();
 
+        self.truing_result = Some(result.unwrap_or_else(|e| e));
+    }
+
+    fn calculate_trajectory(&mut self) {
+        self.error_message = None;
+        self.stability_warning = None;
+
+        let result = self.build_calculator().and_then(|calculator| {
+            let range = self.range.parse::<f64>().map_err(|_| "Invalid range value".to_string())?;
+            calculator.calculate_trajectory(range, 0.01).map_err(|e| e.to_string())
+        });
+
         match result {
             Ok(trajectory) => {
                 self.calculation_results = Some(trajectory);
@@ -251,6 +1086,11 @@ impl BallisticCalculatorApp {
     }
 }
 
+/// Parses a numeric input, naming the field in the error shown to the user.
+fn parse_input(s: &str, field: &str) -> Result<f64, String> {
+    s.parse::<f64>().map_err(|_| format!("Invalid {} value", field))
+}
+
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: ViewportBuilder::default()
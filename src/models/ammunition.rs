@@ -0,0 +1,55 @@
+use serde::{Serialize, Deserialize};
+use crate::models::{Projectile, PowderSensitivity};
+use crate::calculator::{DragModel, StandardDragModel, CustomDragModel};
+
+/// A load: the bullet, how its drag is modeled and how its velocity was quoted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ammunition {
+    pub name: String,
+    pub projectile: Projectile,
+    pub drag_model: StandardDragModel,
+    #[serde(default)]
+    pub custom_drag: Option<CustomDragModel>,  // replaces drag_model when set
+    #[serde(default)]
+    pub test_barrel_length: Option<f64>,  // inches the muzzle velocity was measured with
+    #[serde(default)]
+    pub powder_sensitivity: Option<PowderSensitivity>,
+}
+
+impl Ammunition {
+    pub fn new(name: impl Into<String>, projectile: Projectile, drag_model: StandardDragModel) -> Self {
+        Self {
+            name: name.into(),
+            projectile,
+            drag_model,
+            custom_drag: None,
+            test_barrel_length: None,
+            powder_sensitivity: None,
+        }
+    }
+
+    /// Uses a bullet-specific drag curve instead of the standard drag function.
+    pub fn with_custom_drag(mut self, custom_drag: CustomDragModel) -> Self {
+        self.custom_drag = Some(custom_drag);
+        self
+    }
+
+    /// Records the test barrel the muzzle velocity was quoted for, so that it can be
+    /// adjusted to the rifle's barrel.
+    pub fn with_test_barrel_length(mut self, test_barrel_length: f64) -> Self {
+        self.test_barrel_length = Some(test_barrel_length);
+        self
+    }
+
+    pub fn with_powder_sensitivity(mut self, powder_sensitivity: PowderSensitivity) -> Self {
+        self.powder_sensitivity = Some(powder_sensitivity);
+        self
+    }
+
+    pub fn to_drag_model(&self) -> Box<dyn DragModel> {
+        match &self.custom_drag {
+            Some(custom_drag) => Box::new(custom_drag.clone()),
+            None => self.drag_model.to_model(),
+        }
+    }
+}
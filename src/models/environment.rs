@@ -1,5 +1,22 @@
+use nalgebra::Vector3;
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use crate::models::wind::{WindProfile, horizontal_wind_vector};
+
+// ICAO standard atmosphere constants for the troposphere
+const TEMPERATURE_LAPSE_RATE: f64 = -0.00356616;  // °F per foot
+const PRESSURE_EXPONENT: f64 = 5.255876;  // g·M / (R·L)
+const STANDARD_SEA_LEVEL_TEMP: f64 = 518.67;  // Rankine
+
+/// How `Environment::pressure` was measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PressureReference {
+    /// Absolute pressure at the firing point, e.g. from a Kestrel.
+    #[default]
+    Station,
+    /// Sea level corrected pressure as reported by weather services.
+    Barometric,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
@@ -7,9 +24,17 @@ pub struct Environment {
     pub pressure: f64,       // inHg
     pub humidity: f64,       // %
     pub wind_speed: f64,     // mph
-    pub wind_angle: f64,     // degrees
+    pub wind_angle: f64,     // degrees, direction the wind blows toward: 0 = tailwind, 90 = left to right
     pub altitude: f64,       // feet
     pub latitude: f64,       // degrees
+    #[serde(default)]
+    pub pressure_reference: PressureReference,
+    #[serde(default)]
+    pub wind_profile: Option<WindProfile>,  // replaces wind_speed/wind_angle along the path when set
+    #[serde(default)]
+    pub vertical_wind: f64,  // mph, positive updraft
+    #[serde(default)]
+    pub powder_temperature: Option<f64>,  // °F, defaults to the air temperature
 }
 
 impl Environment {
@@ -23,13 +48,13 @@ impl Environment {
         latitude: f64,
     ) -> Result<Self, EnvironmentError> {
         // Validate inputs
-        if humidity < 0.0 || humidity > 100.0 {
+        if !(0.0..=100.0).contains(&humidity) {
             return Err(EnvironmentError::InvalidHumidity);
         }
-        if wind_angle < 0.0 || wind_angle > 360.0 {
+        if !(0.0..=360.0).contains(&wind_angle) {
             return Err(EnvironmentError::InvalidWindAngle);
         }
-        if latitude < -90.0 || latitude > 90.0 {
+        if !(-90.0..=90.0).contains(&latitude) {
             return Err(EnvironmentError::InvalidLatitude);
         }
 
@@ -41,30 +66,183 @@ impl Environment {
             wind_angle,
             altitude,
             latitude,
+            pressure_reference: PressureReference::Station,
+            wind_profile: None,
+            vertical_wind: 0.0,
+            powder_temperature: None,
         })
     }
 
+    /// ICAO standard atmosphere (59°F, 29.92 inHg, dry air at sea level) at `altitude` feet.
+    pub fn icao_standard(altitude: f64) -> Self {
+        Self::standard_at(altitude, 29.92, 0.0)
+    }
+
+    /// Army Standard Metro (59°F, 29.5275 inHg, 78% humidity at sea level) at `altitude` feet.
+    pub fn army_standard_metro(altitude: f64) -> Self {
+        Self::standard_at(altitude, 29.5275, 78.0)
+    }
+
+    /// ICAO atmosphere with the same air density as the given density altitude (feet).
+    pub fn from_density_altitude(density_altitude: f64) -> Self {
+        Self::icao_standard(density_altitude)
+    }
+
+    fn standard_at(altitude: f64, sea_level_pressure: f64, humidity: f64) -> Self {
+        Self {
+            temperature: STANDARD_SEA_LEVEL_TEMP - 459.67 + TEMPERATURE_LAPSE_RATE * altitude,
+            pressure: sea_level_pressure * standard_pressure_ratio(altitude),
+            humidity,
+            wind_speed: 0.0,
+            wind_angle: 0.0,
+            altitude,
+            latitude: 0.0,
+            pressure_reference: PressureReference::Station,
+            wind_profile: None,
+            vertical_wind: 0.0,
+            powder_temperature: None,
+        }
+    }
+
+    /// Altitude (feet) in the ICAO standard atmosphere with the same air density.
+    pub fn density_altitude(&self) -> f64 {
+        let density_ratio = self.air_density() / 0.0764742;
+        STANDARD_SEA_LEVEL_TEMP / TEMPERATURE_LAPSE_RATE
+            * (density_ratio.powf(1.0 / (PRESSURE_EXPONENT - 1.0)) - 1.0)
+    }
+
+    pub fn with_pressure_reference(mut self, pressure_reference: PressureReference) -> Self {
+        self.pressure_reference = pressure_reference;
+        self
+    }
+
+    /// Uses wind that varies by range segment instead of the single wind.
+    pub fn with_wind_profile(mut self, wind_profile: WindProfile) -> Self {
+        self.wind_profile = Some(wind_profile);
+        self
+    }
+
+    /// Sets the updraft (positive) or downdraft (negative) in mph, e.g. along a slope.
+    pub fn with_vertical_wind(mut self, vertical_wind: f64) -> Self {
+        self.vertical_wind = vertical_wind;
+        self
+    }
+
+    /// Sets the ammunition temperature (°F) when it differs from the air, e.g. after
+    /// sitting in a hot chamber or in the sun.
+    pub fn with_powder_temperature(mut self, powder_temperature: f64) -> Self {
+        self.powder_temperature = Some(powder_temperature);
+        self
+    }
+
+    /// The same atmosphere in still air, e.g. for solving a zero.
+    pub fn without_wind(&self) -> Self {
+        Self {
+            wind_speed: 0.0,
+            wind_profile: None,
+            vertical_wind: 0.0,
+            ..self.clone()
+        }
+    }
+
+    /// Temperature of the powder in °F.
+    pub fn powder_temperature(&self) -> f64 {
+        self.powder_temperature.unwrap_or(self.temperature)
+    }
+
+    /// Vertical wind in ft/s.
+    pub fn vertical_wind_fps(&self) -> f64 {
+        self.vertical_wind * 5280.0 / 3600.0
+    }
+
+    /// Wind velocity at the muzzle in ft/s as (downrange, vertical, lateral) components.
+    pub fn wind_vector(&self) -> Vector3<f64> {
+        self.wind_vector_at(0.0)
+    }
+
+    /// Wind velocity in ft/s at `range` feet downrange, taken from the wind
+    /// profile when one is set, plus the vertical wind.
+    pub fn wind_vector_at(&self, range: f64) -> Vector3<f64> {
+        let horizontal = match &self.wind_profile {
+            Some(profile) => profile.zone_at(range / 3.0).wind_vector(),
+            None => horizontal_wind_vector(self.wind_speed, self.wind_angle),
+        };
+        horizontal + Vector3::new(0.0, self.vertical_wind_fps(), 0.0)
+    }
+
+    /// Absolute pressure at the firing point in inHg.
+    pub fn station_pressure(&self) -> f64 {
+        match self.pressure_reference {
+            PressureReference::Station => self.pressure,
+            PressureReference::Barometric => self.pressure * standard_pressure_ratio(self.altitude),
+        }
+    }
+
+    /// Sea level corrected pressure in inHg.
+    pub fn barometric_pressure(&self) -> f64 {
+        match self.pressure_reference {
+            PressureReference::Station => self.pressure / standard_pressure_ratio(self.altitude),
+            PressureReference::Barometric => self.pressure,
+        }
+    }
+
     pub fn air_density(&self) -> f64 {
-        let temp_r = self.temperature + 459.67;  // Convert to Rankine
+        self.air_density_at(0.0)
+    }
+
+    /// Air density in lb/ft³ at `height` feet above the firing point, following
+    /// the standard temperature lapse rate and hydrostatic pressure drop.
+    pub fn air_density_at(&self, height: f64) -> f64 {
+        let (temperature, pressure) = self.conditions_at(height);
+        let temp_r = temperature + 459.67;  // Convert to Rankine
         let standard_pressure = 29.92;  // inHg
-        let standard_temp = 518.67;  // Rankine
         
-        // Corrected density formula for imperial units
-        let density = self.pressure / standard_pressure * standard_temp / temp_r * 0.0751;  // lb/ft³
+        // Dry air density scaled from the ICAO sea level value (1.225 kg/m³)
+        let density = pressure / standard_pressure * STANDARD_SEA_LEVEL_TEMP / temp_r * 0.0764742;  // lb/ft³
         
-        // Apply humidity correction
-        let water_vapor_pressure = self.calculate_vapor_pressure();
-        let dry_air_pressure = self.pressure - water_vapor_pressure;
-        let correction_factor = (dry_air_pressure + 0.3783 * water_vapor_pressure) / self.pressure;
+        // Apply humidity correction: water vapor is lighter than the dry air it displaces
+        let water_vapor_pressure = self.calculate_vapor_pressure(temperature);
+        let correction_factor = (pressure - 0.378 * water_vapor_pressure) / pressure;
         
         density * correction_factor
     }
 
-    fn calculate_vapor_pressure(&self) -> f64 {
-        let temp_c = (self.temperature - 32.0) * 5.0 / 9.0;
-        let es = 6.11 * 10.0_f64.powf((7.5 * temp_c) / (237.3 + temp_c));
-        (self.humidity / 100.0) * es
+    pub fn speed_of_sound(&self) -> f64 {
+        self.speed_of_sound_at(0.0)
     }
+
+    /// Speed of sound in ft/s at `height` feet above the firing point. Humidity
+    /// raises it slightly, so it is computed from the virtual temperature of the moist air.
+    pub fn speed_of_sound_at(&self, height: f64) -> f64 {
+        let (temperature, pressure) = self.conditions_at(height);
+        let temp_r = temperature + 459.67;
+        let virtual_temp_r = temp_r / (1.0 - 0.378 * self.calculate_vapor_pressure(temperature) / pressure);
+        49.0223 * virtual_temp_r.sqrt()
+    }
+
+    /// Temperature (°F) and station pressure (inHg) at `height` feet above the firing point
+    fn conditions_at(&self, height: f64) -> (f64, f64) {
+        let pressure = self.station_pressure();
+        if height == 0.0 {
+            return (self.temperature, pressure);
+        }
+
+        let temp_r = self.temperature + 459.67;
+        let temp_at_height = temp_r + TEMPERATURE_LAPSE_RATE * height;
+        (temp_at_height - 459.67, pressure * (temp_at_height / temp_r).powf(PRESSURE_EXPONENT))
+    }
+
+    /// Partial pressure of water vapor in inHg
+    fn calculate_vapor_pressure(&self, temperature: f64) -> f64 {
+        let temp_c = (temperature - 32.0) * 5.0 / 9.0;
+        let es = 6.11 * 10.0_f64.powf((7.5 * temp_c) / (237.3 + temp_c));  // hPa
+        (self.humidity / 100.0) * es / 33.8639
+    }
+}
+
+/// Ratio of standard pressure at `altitude` feet to standard sea level pressure
+fn standard_pressure_ratio(altitude: f64) -> f64 {
+    (1.0 + TEMPERATURE_LAPSE_RATE * altitude / STANDARD_SEA_LEVEL_TEMP).powf(PRESSURE_EXPONENT)
 }
 
 #[derive(Debug, Error)]
@@ -1,7 +1,19 @@
 mod projectile;
 mod environment;
 mod trajectory;
+mod wind;
+mod powder;
+mod rifle;
+mod scope;
+mod ammunition;
+mod profile;
 
 pub use projectile::*;
 pub use environment::*;
-pub use trajectory::*;
\ No newline at end of file
+pub use trajectory::*;
+pub use wind::*;
+pub use powder::*;
+pub use rifle::*;
+pub use scope::*;
+pub use ammunition::*;
+pub use profile::*;
\ No newline at end of file
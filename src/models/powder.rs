@@ -0,0 +1,113 @@
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+
+/// How a load's muzzle velocity changes with the temperature of the powder,
+/// which can differ from the air temperature, e.g. for ammo left in the sun.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "PowderSensitivityData")]
+pub enum PowderSensitivity {
+    /// A constant change per degree around a reference measurement.
+    Linear {
+        reference_temperature: f64,  // °F
+        reference_velocity: f64,     // ft/s
+        fps_per_degree: f64,
+    },
+    /// Chronographed (temperature °F, velocity ft/s) pairs sorted by temperature,
+    /// interpolated linearly and extended along the end segments.
+    Table(Vec<(f64, f64)>),
+}
+
+/// Serialized form of a sensitivity, checked by `linear` and `table` on the way in.
+#[derive(Deserialize)]
+enum PowderSensitivityData {
+    Linear {
+        reference_temperature: f64,
+        reference_velocity: f64,
+        fps_per_degree: f64,
+    },
+    Table(Vec<(f64, f64)>),
+}
+
+impl TryFrom<PowderSensitivityData> for PowderSensitivity {
+    type Error = PowderError;
+
+    fn try_from(data: PowderSensitivityData) -> Result<Self, Self::Error> {
+        match data {
+            PowderSensitivityData::Linear { reference_temperature, reference_velocity, fps_per_degree } => {
+                Self::linear(reference_temperature, reference_velocity, fps_per_degree)
+            }
+            PowderSensitivityData::Table(points) => Self::table(points),
+        }
+    }
+}
+
+impl PowderSensitivity {
+    pub fn linear(reference_temperature: f64, reference_velocity: f64, fps_per_degree: f64) -> Result<Self, PowderError> {
+        if !reference_temperature.is_finite() || !fps_per_degree.is_finite()
+            || !reference_velocity.is_finite() || reference_velocity <= 0.0
+        {
+            return Err(PowderError::InvalidParameters);
+        }
+
+        Ok(PowderSensitivity::Linear {
+            reference_temperature,
+            reference_velocity,
+            fps_per_degree,
+        })
+    }
+
+    pub fn table(mut points: Vec<(f64, f64)>) -> Result<Self, PowderError> {
+        if points.len() < 2 {
+            return Err(PowderError::TooFewPoints);
+        }
+        if points.iter().any(|&(temperature, velocity)| !temperature.is_finite() || !velocity.is_finite() || velocity <= 0.0) {
+            return Err(PowderError::InvalidParameters);
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if points.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(PowderError::DuplicateTemperature);
+        }
+
+        Ok(PowderSensitivity::Table(points))
+    }
+
+    /// The same sensitivity with every velocity changed by `change` ft/s, e.g. after
+    /// truing the muzzle velocity. Fails if a velocity would no longer be positive.
+    pub fn shifted(&self, change: f64) -> Result<Self, PowderError> {
+        match self {
+            PowderSensitivity::Linear { reference_temperature, reference_velocity, fps_per_degree } => {
+                Self::linear(*reference_temperature, reference_velocity + change, *fps_per_degree)
+            }
+            PowderSensitivity::Table(points) => {
+                Self::table(points.iter().map(|&(temperature, velocity)| (temperature, velocity + change)).collect())
+            }
+        }
+    }
+
+    /// Muzzle velocity (ft/s) with the powder at `temperature` °F.
+    pub fn muzzle_velocity_at(&self, temperature: f64) -> f64 {
+        match self {
+            PowderSensitivity::Linear { reference_temperature, reference_velocity, fps_per_degree } => {
+                reference_velocity + fps_per_degree * (temperature - reference_temperature)
+            }
+            PowderSensitivity::Table(points) => {
+                // Segment containing the temperature, or the nearest end segment
+                let i = points
+                    .partition_point(|&(t, _)| t <= temperature)
+                    .clamp(1, points.len() - 1);
+                let ((t0, v0), (t1, v1)) = (points[i - 1], points[i]);
+                v0 + (v1 - v0) * (temperature - t0) / (t1 - t0)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PowderError {
+    #[error("Temperatures must be finite and velocities positive")]
+    InvalidParameters,
+    #[error("Powder temperature table needs at least two points")]
+    TooFewPoints,
+    #[error("Powder temperature table has a repeated temperature")]
+    DuplicateTemperature,
+}
@@ -0,0 +1,22 @@
+use serde::{Serialize, Deserialize};
+use crate::models::{Rifle, Scope, Ammunition};
+
+/// A complete setup the calculator can be built from: rifle, scope and load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub rifle: Rifle,
+    pub scope: Scope,
+    pub ammunition: Ammunition,
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>, rifle: Rifle, scope: Scope, ammunition: Ammunition) -> Self {
+        Self {
+            name: name.into(),
+            rifle,
+            scope,
+            ammunition,
+        }
+    }
+}
@@ -1,32 +1,77 @@
+use std::fmt;
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use crate::models::Environment;
 use crate::utils::conversions;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ProjectileData")]
 pub struct Projectile {
     pub weight_grains: f64,
-    pub ballistic_coefficient: f64,
+    /// Needed by the standard drag models; bullet-specific drag curves fly without one.
+    pub ballistic_coefficient: Option<BallisticCoefficient>,
     pub muzzle_velocity: f64,
     pub caliber: f64,
     pub length: f64,
 }
 
+/// Serialized form of a projectile, checked by its constructors on the way in.
+#[derive(Deserialize)]
+struct ProjectileData {
+    weight_grains: f64,
+    #[serde(default)]
+    ballistic_coefficient: Option<BallisticCoefficient>,
+    muzzle_velocity: f64,
+    caliber: f64,
+    length: f64,
+}
+
+impl TryFrom<ProjectileData> for Projectile {
+    type Error = ProjectileError;
+
+    fn try_from(data: ProjectileData) -> Result<Self, Self::Error> {
+        match data.ballistic_coefficient {
+            Some(bc) => Self::new(data.weight_grains, bc, data.muzzle_velocity, data.caliber, data.length),
+            None => Self::without_ballistic_coefficient(data.weight_grains, data.muzzle_velocity, data.caliber, data.length),
+        }
+    }
+}
+
 impl Projectile {
     pub fn new(
         weight_grains: f64,
-        bc: f64,
+        bc: impl Into<BallisticCoefficient>,
+        mv: f64,
+        caliber: f64,
+        length: f64
+    ) -> Result<Self, ProjectileError> {
+        let bc = bc.into();
+        if !bc.is_valid() {
+            return Err(ProjectileError::InvalidBallisticCoefficient);
+        }
+
+        Ok(Self {
+            ballistic_coefficient: Some(bc),
+            ..Self::without_ballistic_coefficient(weight_grains, mv, caliber, length)?
+        })
+    }
+
+    /// A projectile for a bullet-specific drag curve, which scales drag by sectional
+    /// density and so needs no BC.
+    pub fn without_ballistic_coefficient(
+        weight_grains: f64,
         mv: f64,
         caliber: f64,
         length: f64
     ) -> Result<Self, ProjectileError> {
         // Validate inputs
-        if weight_grains <= 0.0 || bc <= 0.0 || mv <= 0.0 || caliber <= 0.0 || length <= 0.0 {
+        if weight_grains <= 0.0 || mv <= 0.0 || caliber <= 0.0 || length <= 0.0 {
             return Err(ProjectileError::InvalidParameters);
         }
-        
+
         Ok(Self {
             weight_grains,
-            ballistic_coefficient: bc,
+            ballistic_coefficient: None,
             muzzle_velocity: mv,
             caliber,
             length,
@@ -41,13 +86,187 @@ impl Projectile {
         self.weight_grains / (7000.0 * self.caliber.powi(2))
     }
 
+    /// Form factor i = SD / BC: how the bullet's drag compares to the reference
+    /// projectile of its BC's drag model. Banded BCs use the muzzle velocity band.
+    /// `None` without a BC.
+    pub fn form_factor(&self) -> Option<f64> {
+        self.ballistic_coefficient
+            .as_ref()
+            .map(|bc| self.sectional_density() / bc.at_velocity(self.muzzle_velocity))
+    }
+
     pub fn initial_velocity_mps(&self) -> f64 {
         conversions::fps_to_mps(self.muzzle_velocity)
     }
+
+    /// Miller gyroscopic stability factor (Sg) for the given barrel twist,
+    /// corrected for muzzle velocity and air density.
+    pub fn miller_stability(&self, twist: &Twist, environment: &Environment) -> f64 {
+        let twist_calibers = twist.rate / self.caliber;
+        let length_calibers = self.length / self.caliber;
+
+        let sg = 30.0 * self.weight_grains
+            / (twist_calibers.powi(2) * self.caliber.powi(3) * length_calibers * (1.0 + length_calibers.powi(2)));
+        let velocity_correction = (self.muzzle_velocity / 2800.0).powf(1.0 / 3.0);
+        let atmosphere_correction = (environment.temperature + 459.67) / 518.67 * 29.92 / environment.station_pressure();
+
+        sg * velocity_correction * atmosphere_correction
+    }
+}
+
+/// Ballistic coefficient (lb/in²) against the drag model's reference projectile,
+/// either a single value or the stepped values some makers publish per velocity band.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged, try_from = "BallisticCoefficientData")]
+pub enum BallisticCoefficient {
+    Single(f64),
+    /// (lower velocity threshold ft/s, BC) pairs sorted by descending threshold.
+    /// Each BC applies at and above its threshold; the last also applies below it.
+    Banded(Vec<(f64, f64)>),
+}
+
+/// Serialized form of a BC, checked like `Projectile::new` and `BallisticCoefficient::banded`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BallisticCoefficientData {
+    Single(f64),
+    Banded(Vec<(f64, f64)>),
+}
+
+impl TryFrom<BallisticCoefficientData> for BallisticCoefficient {
+    type Error = ProjectileError;
+
+    fn try_from(data: BallisticCoefficientData) -> Result<Self, Self::Error> {
+        match data {
+            BallisticCoefficientData::Single(bc) => {
+                let bc = BallisticCoefficient::Single(bc);
+                if !bc.is_valid() {
+                    return Err(ProjectileError::InvalidBallisticCoefficient);
+                }
+                Ok(bc)
+            }
+            BallisticCoefficientData::Banded(bands) => BallisticCoefficient::banded(bands),
+        }
+    }
+}
+
+impl BallisticCoefficient {
+    /// Builds velocity bands from (threshold ft/s, BC) pairs in any order.
+    pub fn banded(mut bands: Vec<(f64, f64)>) -> Result<Self, ProjectileError> {
+        bands.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let bc = BallisticCoefficient::Banded(bands);
+        if !bc.is_valid() {
+            return Err(ProjectileError::InvalidBallisticCoefficient);
+        }
+        Ok(bc)
+    }
+
+    /// The BC in effect at `velocity` ft/s.
+    pub fn at_velocity(&self, velocity: f64) -> f64 {
+        match self {
+            BallisticCoefficient::Single(bc) => *bc,
+            BallisticCoefficient::Banded(bands) => bands
+                .iter()
+                .find(|&&(threshold, _)| velocity >= threshold)
+                .or(bands.last())
+                .map_or(0.0, |&(_, bc)| bc),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            BallisticCoefficient::Single(bc) => bc.is_finite() && *bc > 0.0,
+            BallisticCoefficient::Banded(bands) => {
+                !bands.is_empty()
+                    && bands.iter().all(|&(threshold, bc)| threshold.is_finite() && threshold >= 0.0 && bc.is_finite() && bc > 0.0)
+                    && bands.windows(2).all(|pair| pair[1].0 < pair[0].0)
+            }
+        }
+    }
+}
+
+impl From<f64> for BallisticCoefficient {
+    fn from(bc: f64) -> Self {
+        BallisticCoefficient::Single(bc)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TwistDirection {
+    #[default]
+    Right,
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "TwistData")]
+pub struct Twist {
+    pub rate: f64,  // inches per turn
+    pub direction: TwistDirection,
+}
+
+/// Serialized form of a twist, checked by `Twist::new` on the way in.
+#[derive(Deserialize)]
+struct TwistData {
+    rate: f64,
+    direction: TwistDirection,
+}
+
+impl TryFrom<TwistData> for Twist {
+    type Error = ProjectileError;
+
+    fn try_from(data: TwistData) -> Result<Self, Self::Error> {
+        Self::new(data.rate, data.direction)
+    }
+}
+
+impl Twist {
+    pub fn new(rate: f64, direction: TwistDirection) -> Result<Self, ProjectileError> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(ProjectileError::InvalidParameters);
+        }
+
+        Ok(Self { rate, direction })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StabilityWarning {
+    /// Sg below 1.0: the bullet will not fly point forward.
+    Unstable(f64),
+    /// Sg below 1.4: stable, but with reduced BC and accuracy.
+    Marginal(f64),
+}
+
+impl StabilityWarning {
+    pub fn from_stability(sg: f64) -> Option<Self> {
+        if sg < 1.0 {
+            Some(StabilityWarning::Unstable(sg))
+        } else if sg < 1.4 {
+            Some(StabilityWarning::Marginal(sg))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for StabilityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StabilityWarning::Unstable(sg) => write!(f, "Bullet is unstable (Sg {:.2}), use a faster twist", sg),
+            StabilityWarning::Marginal(sg) => write!(f, "Bullet is marginally stable (Sg {:.2})", sg),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum ProjectileError {
     #[error("Invalid parameters provided")]
     InvalidParameters,
+    #[error("Ballistic coefficients must be positive, with distinct non-negative band velocities")]
+    InvalidBallisticCoefficient,
+    #[error("Muzzle velocity must be positive, got {0:.0} ft/s")]
+    InvalidMuzzleVelocity(f64),
+    #[error("The drag model needs a ballistic coefficient")]
+    MissingBallisticCoefficient,
 }
\ No newline at end of file
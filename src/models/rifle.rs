@@ -0,0 +1,114 @@
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+use crate::models::Twist;
+
+/// How muzzle velocity changes with barrel length.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BarrelVelocityRule {
+    /// A constant change per inch, e.g. the common 25 fps rule of thumb.
+    PerInch(f64),
+    /// v = intercept + slope·ln(length) fitted to chronograph data, capturing the
+    /// smaller gains from each extra inch of a long barrel.
+    Logarithmic { intercept: f64, slope: f64 },
+}
+
+impl BarrelVelocityRule {
+    /// Least squares fit of the logarithmic rule to (barrel length in, velocity ft/s)
+    /// samples, e.g. the same load chronographed from several rifles or a cut-down barrel.
+    pub fn fit(samples: &[(f64, f64)]) -> Result<Self, BarrelError> {
+        if samples.len() < 2 {
+            return Err(BarrelError::TooFewLengths);
+        }
+        if samples.iter().any(|&(length, velocity)| !length.is_finite() || length <= 0.0 || !velocity.is_finite() || velocity <= 0.0) {
+            return Err(BarrelError::InvalidSample);
+        }
+
+        let n = samples.len() as f64;
+        let mean_log = samples.iter().map(|&(length, _)| length.ln()).sum::<f64>() / n;
+        let mean_velocity = samples.iter().map(|&(_, velocity)| velocity).sum::<f64>() / n;
+        let spread: f64 = samples.iter().map(|&(length, _)| (length.ln() - mean_log).powi(2)).sum();
+        if spread == 0.0 {
+            return Err(BarrelError::TooFewLengths);
+        }
+
+        let slope = samples
+            .iter()
+            .map(|&(length, velocity)| (length.ln() - mean_log) * (velocity - mean_velocity))
+            .sum::<f64>()
+            / spread;
+
+        Ok(BarrelVelocityRule::Logarithmic {
+            intercept: mean_velocity - slope * mean_log,
+            slope,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "BarrelData")]
+pub struct Barrel {
+    pub length: f64,  // inches
+    pub velocity_rule: BarrelVelocityRule,
+}
+
+/// Serialized form of a barrel, checked by `Barrel::new` on the way in.
+#[derive(Deserialize)]
+struct BarrelData {
+    length: f64,
+    velocity_rule: BarrelVelocityRule,
+}
+
+impl TryFrom<BarrelData> for Barrel {
+    type Error = BarrelError;
+
+    fn try_from(data: BarrelData) -> Result<Self, Self::Error> {
+        Self::new(data.length, data.velocity_rule)
+    }
+}
+
+impl Barrel {
+    pub fn new(length: f64, velocity_rule: BarrelVelocityRule) -> Result<Self, BarrelError> {
+        if !length.is_finite() || length <= 0.0 {
+            return Err(BarrelError::InvalidLength);
+        }
+
+        Ok(Self { length, velocity_rule })
+    }
+
+    /// Change in muzzle velocity (ft/s) from a velocity quoted for a `reference_length`
+    /// inch test barrel to this barrel.
+    pub fn velocity_change(&self, reference_length: f64) -> f64 {
+        match self.velocity_rule {
+            BarrelVelocityRule::PerInch(fps_per_inch) => fps_per_inch * (self.length - reference_length),
+            BarrelVelocityRule::Logarithmic { slope, .. } => slope * (self.length / reference_length).ln(),
+        }
+    }
+}
+
+/// The gun itself: what the barrel does to the load's velocity and spin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rifle {
+    pub name: String,
+    pub barrel: Barrel,
+    pub twist: Twist,
+}
+
+impl Rifle {
+    pub fn new(name: impl Into<String>, barrel: Barrel, twist: Twist) -> Self {
+        Self {
+            name: name.into(),
+            barrel,
+            twist,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BarrelError {
+    #[error("Barrel length must be positive")]
+    InvalidLength,
+    #[error("Barrel samples need positive lengths and velocities")]
+    InvalidSample,
+    #[error("Fitting needs samples from at least two barrel lengths")]
+    TooFewLengths,
+}
@@ -0,0 +1,97 @@
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+use crate::models::TrajectoryPoint;
+
+/// Angular unit of the scope's turrets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AdjustmentUnit {
+    #[default]
+    Moa,
+    Mil,
+}
+
+impl AdjustmentUnit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdjustmentUnit::Moa => "MOA",
+            AdjustmentUnit::Mil => "MIL",
+        }
+    }
+}
+
+/// The sight as mounted on the rifle, with the range it is zeroed at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ScopeData")]
+pub struct Scope {
+    pub name: String,
+    pub sight_height: f64,   // inches above bore
+    pub zero_range: f64,     // yards
+    pub unit: AdjustmentUnit,
+    pub elevation_click: f64,  // turret units per click
+    pub windage_click: f64,    // turret units per click
+}
+
+/// Serialized form of a scope, checked by `Scope::new` on the way in.
+#[derive(Deserialize)]
+struct ScopeData {
+    name: String,
+    sight_height: f64,
+    zero_range: f64,
+    unit: AdjustmentUnit,
+    elevation_click: f64,
+    windage_click: f64,
+}
+
+impl TryFrom<ScopeData> for Scope {
+    type Error = ScopeError;
+
+    fn try_from(data: ScopeData) -> Result<Self, Self::Error> {
+        Self::new(data.name, data.sight_height, data.zero_range, data.unit, data.elevation_click, data.windage_click)
+    }
+}
+
+impl Scope {
+    pub fn new(
+        name: impl Into<String>,
+        sight_height: f64,
+        zero_range: f64,
+        unit: AdjustmentUnit,
+        elevation_click: f64,
+        windage_click: f64,
+    ) -> Result<Self, ScopeError> {
+        // Validate inputs
+        if !sight_height.is_finite() || sight_height < 0.0 || !zero_range.is_finite() || zero_range <= 0.0 {
+            return Err(ScopeError::InvalidMounting);
+        }
+        if !elevation_click.is_finite() || elevation_click <= 0.0 || !windage_click.is_finite() || windage_click <= 0.0 {
+            return Err(ScopeError::InvalidClickValue);
+        }
+
+        Ok(Self {
+            name: name.into(),
+            sight_height,
+            zero_range,
+            unit,
+            elevation_click,
+            windage_click,
+        })
+    }
+
+    /// Clicks to dial (elevation up, windage right) to bring the point of impact
+    /// onto the line of sight at this point's range.
+    pub fn clicks(&self, point: &TrajectoryPoint) -> (f64, f64) {
+        let (drop, windage) = match self.unit {
+            AdjustmentUnit::Moa => point.moa_adjustment(),
+            AdjustmentUnit::Mil => point.mil_adjustment(),
+        };
+        (-drop / self.elevation_click, -windage / self.windage_click)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ScopeError {
+    #[error("Sight height must be non-negative and zero range must be positive")]
+    InvalidMounting,
+    #[error("Click values must be positive")]
+    InvalidClickValue,
+}
@@ -2,12 +2,32 @@ use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrajectoryPoint {
-    pub distance: f64,    // yards
+    pub distance: f64,    // yards along the line of sight (slant range)
     pub drop: f64,        // inches
     pub windage: f64,     // inches
     pub velocity: f64,    // ft/s
     pub energy: f64,      // ft-lbs
     pub time: f64,        // seconds
+    #[serde(default)]
+    pub mach: f64,
+    #[serde(default)]
+    pub coriolis_vertical: f64,    // inches, included in drop
+    #[serde(default)]
+    pub coriolis_horizontal: f64,  // inches, included in windage
+    #[serde(default)]
+    pub spin_drift: f64,           // inches, included in windage
+    #[serde(default)]
+    pub aerodynamic_jump: f64,     // inches, included in drop
+    #[serde(default)]
+    pub horizontal_distance: f64,  // yards
+    #[serde(default)]
+    pub elevation_correction: f64, // inches between line of sight and bore line when dialed for this range
+    #[serde(default)]
+    pub cant_vertical: f64,        // inches, included in drop
+    #[serde(default)]
+    pub cant_horizontal: f64,      // inches, included in windage
+    #[serde(default)]
+    pub vertical_wind: f64,        // inches, lag rule estimate of the updraft or downdraft share of the drop
 }
 
 impl TrajectoryPoint {
@@ -18,6 +38,7 @@ impl TrajectoryPoint {
         velocity: f64,
         energy: f64,
         time: f64,
+        mach: f64,
     ) -> Self {
         Self {
             distance,
@@ -26,6 +47,47 @@ impl TrajectoryPoint {
             velocity,
             energy,
             time,
+            mach,
+            coriolis_vertical: 0.0,
+            coriolis_horizontal: 0.0,
+            spin_drift: 0.0,
+            aerodynamic_jump: 0.0,
+            horizontal_distance: distance,
+            elevation_correction: 0.0,
+            cant_vertical: 0.0,
+            cant_horizontal: 0.0,
+            vertical_wind: 0.0,
+        }
+    }
+
+    /// Vertical and horizontal shift in inches from canting the rifle `cant` degrees
+    /// clockwise with the elevation for this range dialed: the correction swings
+    /// right by its sine and loses height by its cosine.
+    pub fn cant_shift(&self, cant: f64) -> (f64, f64) {
+        let (sin, cos) = cant.to_radians().sin_cos();
+        (self.elevation_correction * (cos - 1.0), self.elevation_correction * sin)
+    }
+
+    /// The point a `fraction` of the way from this point to `next`.
+    pub fn interpolate(&self, next: &TrajectoryPoint, fraction: f64) -> TrajectoryPoint {
+        let lerp = |a: f64, b: f64| a + (b - a) * fraction;
+        TrajectoryPoint {
+            distance: lerp(self.distance, next.distance),
+            drop: lerp(self.drop, next.drop),
+            windage: lerp(self.windage, next.windage),
+            velocity: lerp(self.velocity, next.velocity),
+            energy: lerp(self.energy, next.energy),
+            time: lerp(self.time, next.time),
+            mach: lerp(self.mach, next.mach),
+            coriolis_vertical: lerp(self.coriolis_vertical, next.coriolis_vertical),
+            coriolis_horizontal: lerp(self.coriolis_horizontal, next.coriolis_horizontal),
+            spin_drift: lerp(self.spin_drift, next.spin_drift),
+            aerodynamic_jump: lerp(self.aerodynamic_jump, next.aerodynamic_jump),
+            horizontal_distance: lerp(self.horizontal_distance, next.horizontal_distance),
+            elevation_correction: lerp(self.elevation_correction, next.elevation_correction),
+            cant_vertical: lerp(self.cant_vertical, next.cant_vertical),
+            cant_horizontal: lerp(self.cant_horizontal, next.cant_horizontal),
+            vertical_wind: lerp(self.vertical_wind, next.vertical_wind),
         }
     }
 
@@ -40,4 +102,14 @@ impl TrajectoryPoint {
         let windage_mil = (self.windage / (self.distance * 36.0)) * 1000.0;
         (drop_mil, windage_mil)
     }
-}
\ No newline at end of file
+}
+/// The trajectory linearly interpolated at `distance` yards along the line of sight,
+/// or `None` if it does not get that far.
+pub fn point_at_distance(points: &[TrajectoryPoint], distance: f64) -> Option<TrajectoryPoint> {
+    let index = points.iter().position(|p| p.distance >= distance)?;
+    if index == 0 {
+        return Some(points[0].clone());
+    }
+    let (before, after) = (&points[index - 1], &points[index]);
+    Some(before.interpolate(after, (distance - before.distance) / (after.distance - before.distance)))
+}
@@ -0,0 +1,116 @@
+use nalgebra::Vector3;
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+
+/// Wind blowing over one segment of the range, from `start` to the next zone.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "WindZoneData")]
+pub struct WindZone {
+    pub start: f64,  // yards downrange where this zone begins
+    pub speed: f64,  // mph
+    pub angle: f64,  // degrees, direction the wind blows toward: 0 = tailwind, 90 = left to right
+}
+
+/// Serialized form of a wind zone, checked by `WindZone::new` on the way in.
+#[derive(Deserialize)]
+struct WindZoneData {
+    start: f64,
+    speed: f64,
+    angle: f64,
+}
+
+impl TryFrom<WindZoneData> for WindZone {
+    type Error = WindProfileError;
+
+    fn try_from(data: WindZoneData) -> Result<Self, Self::Error> {
+        Self::new(data.start, data.speed, data.angle)
+    }
+}
+
+impl WindZone {
+    pub fn new(start: f64, speed: f64, angle: f64) -> Result<Self, WindProfileError> {
+        // Validate inputs
+        if !start.is_finite() || start < 0.0 {
+            return Err(WindProfileError::InvalidStart);
+        }
+        if !(0.0..=360.0).contains(&angle) {
+            return Err(WindProfileError::InvalidWindAngle);
+        }
+
+        Ok(Self { start, speed, angle })
+    }
+
+    /// Wind velocity in ft/s as (downrange, vertical, lateral) components.
+    pub fn wind_vector(&self) -> Vector3<f64> {
+        horizontal_wind_vector(self.speed, self.angle)
+    }
+}
+
+/// Wind that changes along the bullet path, e.g. different muzzle, midrange
+/// and target winds. Each zone holds until the next one starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "WindProfileData")]
+pub struct WindProfile {
+    zones: Vec<WindZone>,
+}
+
+/// Serialized form of a wind profile, checked by `WindProfile::new` on the way in.
+#[derive(Deserialize)]
+struct WindProfileData {
+    zones: Vec<WindZone>,
+}
+
+impl TryFrom<WindProfileData> for WindProfile {
+    type Error = WindProfileError;
+
+    fn try_from(data: WindProfileData) -> Result<Self, Self::Error> {
+        Self::new(data.zones)
+    }
+}
+
+impl WindProfile {
+    pub fn new(zones: Vec<WindZone>) -> Result<Self, WindProfileError> {
+        // Validate inputs
+        match zones.first() {
+            None => return Err(WindProfileError::Empty),
+            Some(first) if first.start != 0.0 => return Err(WindProfileError::MissingMuzzleZone),
+            Some(_) => {}
+        }
+        if zones.windows(2).any(|pair| pair[1].start <= pair[0].start) {
+            return Err(WindProfileError::NonMonotonicStart);
+        }
+
+        Ok(Self { zones })
+    }
+
+    pub fn zones(&self) -> &[WindZone] {
+        &self.zones
+    }
+
+    /// The zone covering `range` yards downrange.
+    pub fn zone_at(&self, range: f64) -> &WindZone {
+        let index = self.zones.partition_point(|zone| zone.start <= range);
+        &self.zones[index.saturating_sub(1)]
+    }
+}
+
+/// Wind velocity in ft/s for a horizontal wind of `speed` mph blowing toward `angle` degrees.
+pub(crate) fn horizontal_wind_vector(speed: f64, angle: f64) -> Vector3<f64> {
+    let angle_rad = angle.to_radians();
+    let speed_fps = speed * 5280.0 / 3600.0;
+    Vector3::new(speed_fps * angle_rad.cos(), 0.0, speed_fps * angle_rad.sin())
+}
+
+#[derive(Debug, Error)]
+pub enum WindProfileError {
+    #[error("Wind profile needs at least one zone")]
+    Empty,
+    #[error("The first wind zone must start at the muzzle")]
+    MissingMuzzleZone,
+    #[error("Wind zone starts must be non-negative")]
+    InvalidStart,
+    #[error("Wind zone starts must be strictly increasing")]
+    NonMonotonicStart,
+    #[error("Wind angle must be between 0 and 360")]
+    InvalidWindAngle,
+}
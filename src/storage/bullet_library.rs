@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+use crate::models::{Projectile, ProjectileError};
+use crate::calculator::StandardDragModel;
+
+const BUILTIN_BULLETS: &str = include_str!("../../data/bullets.json");
+
+/// A bullet as published by its maker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulletEntry {
+    pub manufacturer: String,
+    pub model: String,
+    pub caliber: f64,        // inches
+    pub weight_grains: f64,
+    pub length: f64,         // inches
+    #[serde(default)]
+    pub g1_bc: Option<f64>,
+    #[serde(default)]
+    pub g7_bc: Option<f64>,
+}
+
+impl BulletEntry {
+    /// Display name, e.g. `Sierra MatchKing HPBT .308 168gr`.
+    pub fn name(&self) -> String {
+        let caliber = format!("{:.3}", self.caliber);
+        format!("{} {} {} {}gr", self.manufacturer, self.model, caliber.trim_start_matches('0'), self.weight_grains)
+    }
+
+    /// Published BC for the drag model, if the maker lists one.
+    pub fn ballistic_coefficient(&self, drag_model: StandardDragModel) -> Option<f64> {
+        match drag_model {
+            StandardDragModel::G1 => self.g1_bc,
+            StandardDragModel::G7 => self.g7_bc,
+            _ => None,
+        }
+    }
+
+    pub fn to_projectile(&self, drag_model: StandardDragModel, muzzle_velocity: f64) -> Result<Projectile, BulletLibraryError> {
+        let ballistic_coefficient = self
+            .ballistic_coefficient(drag_model)
+            .ok_or_else(|| BulletLibraryError::MissingBallisticCoefficient(drag_model.name()))?;
+        Ok(Projectile::new(self.weight_grains, ballistic_coefficient, muzzle_velocity, self.caliber, self.length)?)
+    }
+
+    fn same_bullet(&self, other: &BulletEntry) -> bool {
+        self.manufacturer.eq_ignore_ascii_case(&other.manufacturer)
+            && self.model.eq_ignore_ascii_case(&other.model)
+            && self.caliber == other.caliber
+            && self.weight_grains == other.weight_grains
+    }
+
+    fn is_valid(&self) -> bool {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        positive(self.caliber)
+            && positive(self.weight_grains)
+            && positive(self.length)
+            && (self.g1_bc.is_some() || self.g7_bc.is_some())
+            && self.g1_bc.into_iter().chain(self.g7_bc).all(positive)
+    }
+}
+
+/// File layout shared by the built-in database and user extension files.
+#[derive(Serialize, Deserialize)]
+struct BulletFile {
+    bullets: Vec<BulletEntry>,
+}
+
+/// Searchable bullet database: the built-in entries plus any merged user files.
+#[derive(Debug, Clone)]
+pub struct BulletLibrary {
+    entries: Vec<BulletEntry>,
+}
+
+impl BulletLibrary {
+    /// The bullets shipped with the program.
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_BULLETS).expect("built-in bullet database is valid")
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self, BulletLibraryError> {
+        let file: BulletFile = serde_json::from_str(contents).map_err(|e| BulletLibraryError::Parse(e.to_string()))?;
+        Self::new(file.bullets)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, BulletLibraryError> {
+        let file: BulletFile = toml::from_str(contents).map_err(|e| BulletLibraryError::Parse(e.to_string()))?;
+        Self::new(file.bullets)
+    }
+
+    fn new(entries: Vec<BulletEntry>) -> Result<Self, BulletLibraryError> {
+        if let Some(entry) = entries.iter().find(|entry| !entry.is_valid()) {
+            return Err(BulletLibraryError::InvalidEntry(entry.name()));
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[BulletEntry] {
+        &self.entries
+    }
+
+    /// Adds the bullets from a `.json` or `.toml` file with a `bullets` list. Entries for
+    /// a bullet already in the library replace it. Returns the number of entries read.
+    pub fn merge_file(&mut self, path: impl AsRef<Path>) -> Result<usize, BulletLibraryError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let other = match extension.as_str() {
+            "json" => Self::from_json(&contents)?,
+            "toml" => Self::from_toml(&contents)?,
+            _ => return Err(BulletLibraryError::UnsupportedFormat(extension)),
+        };
+        let count = other.entries.len();
+        self.merge(other);
+        Ok(count)
+    }
+
+    pub fn merge(&mut self, other: BulletLibrary) {
+        for entry in other.entries {
+            match self.entries.iter_mut().find(|existing| existing.same_bullet(&entry)) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+    }
+
+    /// Entries whose name contains every whitespace separated term, ignoring case,
+    /// e.g. `"hornady .264"` or `"308 175"`.
+    pub fn search(&self, query: &str) -> Vec<&BulletEntry> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let name = entry.name().to_lowercase();
+                terms.iter().all(|term| name.contains(term.trim_start_matches('0')))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BulletLibraryError {
+    #[error("Failed to read bullet file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported bullet file format: {0:?}")]
+    UnsupportedFormat(String),
+    #[error("Could not parse bullet data: {0}")]
+    Parse(String),
+    #[error("Bullet {0} needs a positive caliber, weight, length and at least one BC")]
+    InvalidEntry(String),
+    #[error("No published {0} BC for this bullet")]
+    MissingBallisticCoefficient(&'static str),
+    #[error(transparent)]
+    Projectile(#[from] ProjectileError),
+}
@@ -0,0 +1,5 @@
+mod bullet_library;
+mod profile_store;
+
+pub use bullet_library::*;
+pub use profile_store::*;
\ No newline at end of file
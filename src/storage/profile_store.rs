@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use thiserror::Error;
+use crate::models::Profile;
+
+/// Version written to new profile files. Files without a version are version 0.
+pub const PROFILE_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProfileFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl ProfileFormat {
+    pub const ALL: [ProfileFormat; 2] = [ProfileFormat::Json, ProfileFormat::Toml];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProfileFormat::Json => "JSON",
+            ProfileFormat::Toml => "TOML",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProfileFormat::Json => "json",
+            ProfileFormat::Toml => "toml",
+        }
+    }
+
+    fn from_extension(extension: &str) -> Option<Self> {
+        ProfileFormat::ALL.into_iter().find(|format| format.extension() == extension)
+    }
+}
+
+/// On-disk layout: the profile's fields alongside the schema version.
+#[derive(Serialize)]
+struct VersionedProfile<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    profile: &'a Profile,
+}
+
+/// Saves and loads named profiles as files in a directory, one per profile.
+pub struct ProfileStore {
+    directory: PathBuf,
+}
+
+impl ProfileStore {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Store in the user's configuration directory, e.g. `~/.config/ballistic_calculator/profiles`.
+    pub fn open_default() -> Result<Self, ProfileStoreError> {
+        let directory = dirs::config_dir().ok_or(ProfileStoreError::NoConfigDirectory)?;
+        Ok(Self::new(directory.join("ballistic_calculator").join("profiles")))
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Writes the profile under its name, replacing any saved copy in either format.
+    pub fn save(&self, profile: &Profile, format: ProfileFormat) -> Result<PathBuf, ProfileStoreError> {
+        let file_stem = file_stem(&profile.name)?;
+        fs::create_dir_all(&self.directory)?;
+
+        for other in ProfileFormat::ALL.into_iter().filter(|&other| other != format) {
+            let stale = self.path(&file_stem, other);
+            if stale.exists() {
+                fs::remove_file(stale)?;
+            }
+        }
+
+        let path = self.path(&file_stem, format);
+        fs::write(&path, profile_to_string(profile, format)?)?;
+        Ok(path)
+    }
+
+    /// Reads the named profile, migrating it from older schema versions.
+    pub fn load(&self, name: &str) -> Result<Profile, ProfileStoreError> {
+        let file_stem = file_stem(name)?;
+        for format in ProfileFormat::ALL {
+            let path = self.path(&file_stem, format);
+            if path.exists() {
+                return profile_from_str(&fs::read_to_string(path)?, format);
+            }
+        }
+
+        Err(ProfileStoreError::NotFound(name.to_string()))
+    }
+
+    /// Names of the saved profiles as stored on disk, sorted. Each can be passed to `load`.
+    pub fn list(&self) -> Result<Vec<String>, ProfileStoreError> {
+        if !self.directory.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+            if ProfileFormat::from_extension(extension).is_some() {
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn path(&self, file_stem: &str, format: ProfileFormat) -> PathBuf {
+        self.directory.join(format!("{}.{}", file_stem, format.extension()))
+    }
+}
+
+pub fn profile_to_string(profile: &Profile, format: ProfileFormat) -> Result<String, ProfileStoreError> {
+    let versioned = VersionedProfile {
+        schema_version: PROFILE_SCHEMA_VERSION,
+        profile,
+    };
+
+    match format {
+        ProfileFormat::Json => serde_json::to_string_pretty(&versioned).map_err(|e| ProfileStoreError::Serialize(e.to_string())),
+        ProfileFormat::Toml => toml::to_string(&versioned).map_err(|e| ProfileStoreError::Serialize(e.to_string())),
+    }
+}
+
+pub fn profile_from_str(contents: &str, format: ProfileFormat) -> Result<Profile, ProfileStoreError> {
+    let value: Value = match format {
+        ProfileFormat::Json => serde_json::from_str(contents).map_err(|e| ProfileStoreError::Parse(e.to_string()))?,
+        ProfileFormat::Toml => toml::from_str(contents).map_err(|e| ProfileStoreError::Parse(e.to_string()))?,
+    };
+
+    serde_json::from_value(migrate(value)?).map_err(|e| ProfileStoreError::Parse(e.to_string()))
+}
+
+/// Upgrades a stored profile one schema version at a time to the current layout.
+fn migrate(mut value: Value) -> Result<Value, ProfileStoreError> {
+    let object = value.as_object_mut().ok_or_else(|| ProfileStoreError::Parse("profile is not a table".to_string()))?;
+    let mut version = match object.remove("schema_version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| ProfileStoreError::Parse("schema_version is not a version number".to_string()))?,
+    };
+    if version > PROFILE_SCHEMA_VERSION {
+        return Err(ProfileStoreError::UnsupportedVersion(version));
+    }
+
+    while version < PROFILE_SCHEMA_VERSION {
+        match version {
+            // Version 0 files were written by serializing `Profile` directly and only lack the version
+            0 => {}
+            _ => unreachable!("every older schema version has a migration"),
+        }
+        version += 1;
+    }
+
+    Ok(value)
+}
+
+/// File name for a profile name, keeping it readable but safe on every platform.
+fn file_stem(name: &str) -> Result<String, ProfileStoreError> {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.') { c } else { '_' })
+        .collect();
+    let stem = stem.trim_matches('.').to_string();
+
+    if stem.is_empty() {
+        return Err(ProfileStoreError::InvalidName);
+    }
+    Ok(stem)
+}
+
+#[derive(Debug, Error)]
+pub enum ProfileStoreError {
+    #[error("Failed to access profile: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("No configuration directory for profiles")]
+    NoConfigDirectory,
+    #[error("Profile needs a name")]
+    InvalidName,
+    #[error("Profile {0:?} not found")]
+    NotFound(String),
+    #[error("Could not write profile: {0}")]
+    Serialize(String),
+    #[error("Could not parse profile: {0}")]
+    Parse(String),
+    #[error("Profile schema version {0} is newer than this program supports")]
+    UnsupportedVersion(u32),
+}
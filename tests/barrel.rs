@@ -0,0 +1,56 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    AdjustmentUnit,
+    Ammunition,
+    Barrel,
+    BarrelVelocityRule,
+    CalculatorError,
+    Environment,
+    Profile,
+    Projectile,
+    ProjectileError,
+    Rifle,
+    Scope,
+    StandardDragModel,
+    TrajectoryCalculator,
+    Twist,
+    TwistDirection,
+};
+
+/// ICAO standard sea level atmosphere, no wind.
+fn standard_atmosphere() -> Environment {
+    Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+}
+
+#[test]
+fn shorter_barrel_loses_muzzle_velocity() {
+    let per_inch = Barrel::new(20.0, BarrelVelocityRule::PerInch(25.0)).unwrap();
+    assert_relative_eq!(per_inch.velocity_change(24.0), -100.0);
+
+    // Samples lying exactly on v = 1000 + 500·ln(L) recover the curve
+    let samples: Vec<(f64, f64)> = [16.0, 20.0, 26.0].iter().map(|&l: &f64| (l, 1000.0 + 500.0 * l.ln())).collect();
+    let fitted = Barrel::new(18.0, BarrelVelocityRule::fit(&samples).unwrap()).unwrap();
+    assert_relative_eq!(fitted.velocity_change(24.0), 500.0 * (18.0_f64 / 24.0).ln(), max_relative = 1e-9);
+
+    let projectile = Projectile::new(168.0, 0.462, 2650.0, 0.308, 1.215).unwrap();
+    let calculator = TrajectoryCalculator::new(projectile, standard_atmosphere(), StandardDragModel::G1.to_model())
+        .with_barrel(&per_inch, 24.0)
+        .unwrap();
+    assert_relative_eq!(calculator.projectile().muzzle_velocity, 2550.0);
+
+    // A 4" barrel at 150 fps per inch would leave -400 fps
+    let stub = Barrel::new(4.0, BarrelVelocityRule::PerInch(150.0)).unwrap();
+    let projectile = Projectile::new(168.0, 0.462, 2600.0, 0.308, 1.215).unwrap();
+    let calculator = TrajectoryCalculator::new(projectile.clone(), standard_atmosphere(), StandardDragModel::G1.to_model())
+        .with_barrel(&stub, 24.0);
+    assert!(matches!(calculator, Err(ProjectileError::InvalidMuzzleVelocity(_))));
+
+    let ammunition = Ammunition::new("168gr match", projectile, StandardDragModel::G1).with_test_barrel_length(24.0);
+    let rifle = Rifle::new("stub", stub, Twist::new(10.0, TwistDirection::Right).unwrap());
+    let scope = Scope::new("1/4 MOA", 1.5, 100.0, AdjustmentUnit::Moa, 0.25, 0.25).unwrap();
+    let profile = Profile::new("stub", rifle, scope, ammunition);
+    assert!(matches!(
+        TrajectoryCalculator::from_profile(&profile, standard_atmosphere()),
+        Err(CalculatorError::Projectile(ProjectileError::InvalidMuzzleVelocity(_)))
+    ));
+}
@@ -0,0 +1,76 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    ballistic_coefficient_from_velocities,
+    BallisticCoefficient,
+    convert_ballistic_coefficient,
+    Environment,
+    point_at_distance,
+    Projectile,
+    StandardDragModel,
+    TrajectoryCalculator,
+};
+
+/// ICAO standard sea level atmosphere, no wind.
+fn standard_atmosphere() -> Environment {
+    Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+}
+
+#[test]
+fn banded_ballistic_coefficient_steps_down_with_velocity() {
+    let bands = BallisticCoefficient::banded(vec![(0.0, 0.424), (2200.0, 0.462), (1800.0, 0.447)]).unwrap();
+    assert_eq!(bands.at_velocity(2500.0), 0.462);
+    assert_eq!(bands.at_velocity(2000.0), 0.447);
+    assert_eq!(bands.at_velocity(1000.0), 0.424);
+
+    // Above the top threshold the banded BC flies exactly like the single value
+    let velocity_at = |bc: BallisticCoefficient, yards: f64| {
+        let projectile = Projectile::new(168.0, bc, 2650.0, 0.308, 1.215).unwrap();
+        let points = TrajectoryCalculator::new(projectile, standard_atmosphere(), StandardDragModel::G1.to_model())
+            .calculate_trajectory(yards, 0.001)
+            .unwrap();
+        point_at_distance(&points, yards).unwrap().velocity
+    };
+    assert_relative_eq!(velocity_at(bands.clone(), 200.0), velocity_at(0.462.into(), 200.0), max_relative = 1e-9);
+    assert!(velocity_at(bands, 1000.0) < velocity_at(0.462.into(), 1000.0));
+    assert!(BallisticCoefficient::banded(vec![(2000.0, 0.4), (2000.0, 0.3)]).is_err());
+
+    // Saved BCs are checked on load: bands are sorted, empty or repeated ones refused
+    let loaded: BallisticCoefficient = serde_json::from_str("[[0.0, 0.424], [2200.0, 0.462]]").unwrap();
+    assert_eq!(loaded.at_velocity(2500.0), 0.462);
+    assert_eq!(serde_json::from_str::<BallisticCoefficient>("0.462").unwrap(), BallisticCoefficient::Single(0.462));
+    for invalid in ["[]", "[[2000.0, 0.4], [2000.0, 0.3]]", "[[0.0, -0.4]]", "0.0"] {
+        assert!(serde_json::from_str::<BallisticCoefficient>(invalid).is_err());
+    }
+}
+
+#[test]
+fn g1_to_g7_conversion_matches_time_of_flight() {
+    // A .462 G1 boat tail is about a .23 G7 over typical supersonic velocities
+    let g7 = convert_ballistic_coefficient(0.462, StandardDragModel::G1, StandardDragModel::G7, (2650.0, 1300.0)).unwrap();
+    assert_relative_eq!(g7, 0.236, max_relative = 0.02);
+
+    let identity = convert_ballistic_coefficient(0.462, StandardDragModel::G1, StandardDragModel::G1, (2650.0, 1300.0)).unwrap();
+    assert_relative_eq!(identity, 0.462, max_relative = 1e-4);
+
+    let projectile = Projectile::new(168.0, 0.462, 2650.0, 0.308, 1.215).unwrap();
+    assert_relative_eq!(projectile.form_factor().unwrap(), projectile.sectional_density() / 0.462);
+}
+
+#[test]
+fn chronograph_velocities_recover_ballistic_coefficient() {
+    let projectile = Projectile::new(168.0, 0.462, 2650.0, 0.308, 1.215).unwrap();
+    let points = TrajectoryCalculator::new(projectile, standard_atmosphere(), StandardDragModel::G1.to_model())
+        .calculate_trajectory(300.0, 0.001)
+        .unwrap();
+    let far_velocity = point_at_distance(&points, 300.0).unwrap().velocity;
+
+    let bc = ballistic_coefficient_from_velocities(
+        2650.0,
+        far_velocity,
+        300.0,
+        &standard_atmosphere(),
+        StandardDragModel::G1.to_model().as_ref(),
+    ).unwrap();
+    assert_relative_eq!(bc, 0.462, max_relative = 0.003);
+    assert!(ballistic_coefficient_from_velocities(2000.0, 2650.0, 300.0, &standard_atmosphere(), StandardDragModel::G1.to_model().as_ref()).is_err());
+}
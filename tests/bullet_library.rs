@@ -0,0 +1,38 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{BulletLibrary, StandardDragModel};
+
+#[test]
+fn bullet_library_searches_and_merges_user_entries() {
+    let mut library = BulletLibrary::builtin();
+    let matches = library.search("sierra 308 175");
+    assert_eq!(matches.len(), 1);
+    let projectile = matches[0].to_projectile(StandardDragModel::G7, 2600.0).unwrap();
+    assert_relative_eq!(projectile.ballistic_coefficient.unwrap().at_velocity(2600.0), 0.243);
+    assert!(matches[0].to_projectile(StandardDragModel::G5, 2600.0).is_err());
+
+    // User entries for a known bullet replace it, new ones are added
+    let builtin_count = library.entries().len();
+    let user = BulletLibrary::from_toml(r#"
+        [[bullets]]
+        manufacturer = "Sierra"
+        model = "MatchKing HPBT"
+        caliber = 0.308
+        weight_grains = 175.0
+        length = 1.24
+        g7_bc = 0.250
+
+        [[bullets]]
+        manufacturer = "Custom"
+        model = "Turned Solid"
+        caliber = 0.338
+        weight_grains = 250.0
+        length = 1.6
+        g1_bc = 0.650
+    "#).unwrap();
+    library.merge(user);
+
+    assert_eq!(library.entries().len(), builtin_count + 1);
+    assert_eq!(library.search("sierra 308 175")[0].g7_bc, Some(0.250));
+    assert_eq!(library.search("turned solid").len(), 1);
+    assert!(BulletLibrary::from_json(r#"{"bullets": [{"manufacturer": "X", "model": "Y", "caliber": 0.3, "weight_grains": 150.0, "length": 1.1}]}"#).is_err());
+}
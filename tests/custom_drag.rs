@@ -0,0 +1,75 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    CalculatorError,
+    CustomDragError,
+    CustomDragModel,
+    DragModel,
+    Environment,
+    Projectile,
+    ProjectileError,
+    StandardDragModel,
+    TrajectoryCalculator,
+};
+
+#[test]
+fn csv_skips_header_and_comments() {
+    let drag = CustomDragModel::from_csv("radar", "# Doppler radar, .308 175gr\nmach,cd\n0.5, 0.230\n1.0, 0.400\n\n2.0, 0.300\n").unwrap();
+
+    assert_eq!(drag.name, "radar");
+    assert_eq!(drag.table(), &[(0.5, 0.230), (1.0, 0.400), (2.0, 0.300)]);
+    assert_relative_eq!(drag.get_cd(1.0), 0.400);
+    assert!(matches!(CustomDragModel::from_csv("bad", "mach,cd\n0.5,0.23\ncd,mach\n"), Err(CustomDragError::Parse(_))));
+}
+
+#[test]
+fn drg_reads_header_description_and_cd_mach_rows() {
+    let drag = CustomDragModel::from_drg("CFM 0.01134 0.00782 .308 175gr SMK\n0.230 0.50\n0.400 1.00\n0.300 2.00\n").unwrap();
+
+    assert_eq!(drag.name, ".308 175gr SMK");
+    assert_eq!(drag.table(), &[(0.50, 0.230), (1.00, 0.400), (2.00, 0.300)]);
+    assert!(matches!(CustomDragModel::from_drg(""), Err(CustomDragError::TooFewPoints)));
+}
+
+#[test]
+fn tables_must_have_increasing_mach() {
+    assert!(matches!(
+        CustomDragModel::new("reversed", vec![(1.0, 0.4), (0.5, 0.23)]),
+        Err(CustomDragError::NonMonotonicMach)
+    ));
+    assert!(matches!(
+        CustomDragModel::from_csv("repeated", "0.5,0.23\n0.5,0.25\n"),
+        Err(CustomDragError::NonMonotonicMach)
+    ));
+    assert!(matches!(CustomDragModel::new("single", vec![(1.0, 0.4)]), Err(CustomDragError::TooFewPoints)));
+}
+
+#[test]
+fn deserializing_checks_the_table() {
+    let valid: CustomDragModel = serde_json::from_str(r#"{"name": "radar", "table": [[0.5, 0.23], [1.0, 0.4]]}"#).unwrap();
+    assert_eq!(valid.table().len(), 2);
+
+    // Saved profiles embed the curve, so a bad one must fail to load rather than panic in flight
+    assert!(serde_json::from_str::<CustomDragModel>(r#"{"name": "empty", "table": []}"#).is_err());
+    assert!(serde_json::from_str::<CustomDragModel>(r#"{"name": "reversed", "table": [[1.0, 0.4], [0.5, 0.23]]}"#).is_err());
+    assert!(matches!(
+        CustomDragModel::from_json(r#"{"name": "empty", "table": []}"#),
+        Err(CustomDragError::TooFewPoints)
+    ));
+}
+
+#[test]
+fn only_standard_models_need_a_ballistic_coefficient() {
+    let projectile = Projectile::without_ballistic_coefficient(175.0, 2600.0, 0.308, 1.24).unwrap();
+    let environment = Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+    let drag = CustomDragModel::new("radar", vec![(0.5, 0.23), (1.0, 0.4), (3.0, 0.3)]).unwrap();
+
+    let points = TrajectoryCalculator::new(projectile.clone(), environment.clone(), Box::new(drag))
+        .calculate_trajectory(300.0, 0.01)
+        .unwrap();
+    assert!(points.last().unwrap().velocity < 2600.0);
+
+    assert!(matches!(
+        TrajectoryCalculator::new(projectile, environment, StandardDragModel::G7.to_model()).calculate_trajectory(300.0, 0.01),
+        Err(CalculatorError::Projectile(ProjectileError::MissingBallisticCoefficient))
+    ));
+}
@@ -0,0 +1,51 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    Environment,
+    PowderSensitivity,
+    Projectile,
+    ProjectileError,
+    StandardDragModel,
+    TrajectoryCalculator,
+};
+
+/// ICAO standard sea level atmosphere, no wind.
+fn standard_atmosphere() -> Environment {
+    Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+}
+
+#[test]
+fn powder_temperature_shifts_muzzle_velocity() {
+    let linear = PowderSensitivity::linear(59.0, 2650.0, 1.5).unwrap();
+    assert_relative_eq!(linear.muzzle_velocity_at(99.0), 2710.0);
+
+    let table = PowderSensitivity::table(vec![(90.0, 2700.0), (20.0, 2600.0), (60.0, 2650.0)]).unwrap();
+    assert_relative_eq!(table.muzzle_velocity_at(40.0), 2625.0);
+    assert_relative_eq!(table.muzzle_velocity_at(0.0), 2575.0);  // Extended along the cold end segment
+
+    // Truing the velocity moves the whole curve
+    assert_relative_eq!(table.shifted(-25.0).unwrap().muzzle_velocity_at(40.0), 2600.0);
+    assert_relative_eq!(linear.shifted(10.0).unwrap().muzzle_velocity_at(99.0), 2720.0);
+    assert!(table.shifted(-2600.0).is_err());
+
+    let projectile = Projectile::new(168.0, 0.462, 2650.0, 0.308, 1.215).unwrap();
+    let calculator = TrajectoryCalculator::new(projectile, standard_atmosphere(), StandardDragModel::G1.to_model())
+        .with_powder_temperature(&linear, 19.0)
+        .unwrap();
+    assert_relative_eq!(calculator.projectile().muzzle_velocity, 2590.0);
+
+    // A sensitivity extended far enough to stop the bullet is refused rather than flown backwards
+    let steep = PowderSensitivity::linear(59.0, 2650.0, 30.0).unwrap();
+    let projectile = Projectile::new(168.0, 0.462, 2650.0, 0.308, 1.215).unwrap();
+    let frozen = TrajectoryCalculator::new(projectile, standard_atmosphere(), StandardDragModel::G1.to_model())
+        .with_powder_temperature(&steep, -40.0);
+    assert!(matches!(frozen, Err(ProjectileError::InvalidMuzzleVelocity(_))));
+
+    // Deserializing checks the table like `table`, so a saved profile cannot hold an empty one
+    let loaded: PowderSensitivity = serde_json::from_str(r#"{"Table": [[90.0, 2700.0], [20.0, 2600.0]]}"#).unwrap();
+    assert_relative_eq!(loaded.muzzle_velocity_at(20.0), 2600.0);
+    assert!(serde_json::from_str::<PowderSensitivity>(r#"{"Table": []}"#).is_err());
+    assert!(serde_json::from_str::<PowderSensitivity>(r#"{"Table": [[60.0, 2650.0], [60.0, 2660.0]]}"#).is_err());
+    assert!(serde_json::from_str::<PowderSensitivity>(
+        r#"{"Linear": {"reference_temperature": 59.0, "reference_velocity": 0.0, "fps_per_degree": 1.5}}"#
+    ).is_err());
+}
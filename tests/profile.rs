@@ -0,0 +1,49 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    AdjustmentUnit,
+    Ammunition,
+    Barrel,
+    BarrelVelocityRule,
+    Environment,
+    point_at_distance,
+    PowderSensitivity,
+    Profile,
+    Projectile,
+    Rifle,
+    Scope,
+    StandardDragModel,
+    TrajectoryCalculator,
+    Twist,
+    TwistDirection,
+};
+
+/// ICAO standard sea level atmosphere, no wind.
+fn standard_atmosphere() -> Environment {
+    Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+}
+
+#[test]
+fn profile_builds_zeroed_calculator_with_adjusted_velocity() {
+    let projectile = Projectile::new(168.0, 0.462, 2650.0, 0.308, 1.215).unwrap();
+    let ammunition = Ammunition::new("168gr match", projectile, StandardDragModel::G1)
+        .with_test_barrel_length(24.0)
+        .with_powder_sensitivity(PowderSensitivity::linear(59.0, 2650.0, 1.5).unwrap());
+    let rifle = Rifle::new(
+        "20in .308",
+        Barrel::new(20.0, BarrelVelocityRule::PerInch(25.0)).unwrap(),
+        Twist::new(10.0, TwistDirection::Right).unwrap(),
+    );
+    let scope = Scope::new("1/4 MOA", 1.5, 100.0, AdjustmentUnit::Moa, 0.25, 0.25).unwrap();
+    let profile = Profile::new("match", rifle, scope.clone(), ammunition);
+
+    // 20°F warmer powder gains 30 fps, four inches less barrel loses 100 fps
+    let calculator = TrajectoryCalculator::from_profile(&profile, standard_atmosphere().with_powder_temperature(79.0)).unwrap();
+    assert_relative_eq!(calculator.projectile().muzzle_velocity, 2580.0);
+    assert!(calculator.stability().is_some());
+
+    let points = calculator.calculate_trajectory(500.0, 0.001).unwrap();
+    assert!(point_at_distance(&points, 100.0).unwrap().drop.abs() < 0.02);
+    let (elevation, _) = scope.clicks(&point_at_distance(&points, 500.0).unwrap());
+    assert!(elevation > 0.0);
+    assert_relative_eq!(elevation, -point_at_distance(&points, 500.0).unwrap().moa_adjustment().0 * 4.0);
+}
@@ -0,0 +1,66 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    AdjustmentUnit,
+    Ammunition,
+    Barrel,
+    BarrelVelocityRule,
+    BallisticCoefficient,
+    PowderSensitivity,
+    Profile,
+    ProfileFormat,
+    ProfileStore,
+    ProfileStoreError,
+    profile_from_str,
+    profile_to_string,
+    Projectile,
+    Rifle,
+    Scope,
+    StandardDragModel,
+    Twist,
+    TwistDirection,
+};
+
+#[test]
+fn profiles_round_trip_through_json_and_toml_stores() {
+    let projectile = Projectile::new(168.0, BallisticCoefficient::banded(vec![(2200.0, 0.462), (0.0, 0.447)]).unwrap(), 2650.0, 0.308, 1.215).unwrap();
+    let ammunition = Ammunition::new("168gr match", projectile, StandardDragModel::G1)
+        .with_powder_sensitivity(PowderSensitivity::linear(59.0, 2650.0, 1.5).unwrap());
+    let rifle = Rifle::new(
+        "20in .308",
+        Barrel::new(20.0, BarrelVelocityRule::PerInch(25.0)).unwrap(),
+        Twist::new(10.0, TwistDirection::Right).unwrap(),
+    );
+    let scope = Scope::new("1/10 MIL", 1.5, 100.0, AdjustmentUnit::Mil, 0.1, 0.1).unwrap();
+    let profile = Profile::new("Match .308", rifle, scope, ammunition);
+
+    let directory = std::env::temp_dir().join(format!("ballistic_profiles_{}", std::process::id()));
+    let store = ProfileStore::new(&directory);
+    for format in ProfileFormat::ALL {
+        store.save(&profile, format).unwrap();
+        assert_eq!(store.list().unwrap(), vec!["Match .308".to_string()]);
+
+        let loaded = store.load("Match .308").unwrap();
+        assert_eq!(loaded.ammunition.projectile.ballistic_coefficient, profile.ammunition.projectile.ballistic_coefficient);
+        assert_eq!(loaded.scope.unit, AdjustmentUnit::Mil);
+        assert_relative_eq!(loaded.rifle.twist.rate, 10.0);
+    }
+    std::fs::remove_dir_all(directory).unwrap();
+
+    // Unversioned files predate schema versioning and still load; newer versions are refused
+    let unversioned = serde_json::to_string(&profile).unwrap();
+    assert_eq!(profile_from_str(&unversioned, ProfileFormat::Json).unwrap().name, "Match .308");
+    let future = profile_to_string(&profile, ProfileFormat::Toml).unwrap().replace("schema_version = 1", "schema_version = 99");
+    assert!(matches!(profile_from_str(&future, ProfileFormat::Toml), Err(ProfileStoreError::UnsupportedVersion(99))));
+
+    // Edited files are checked like the GUI inputs, so a zero twist or click cannot load as NaN or infinity
+    let saved = profile_to_string(&profile, ProfileFormat::Json).unwrap();
+    for (valid, invalid) in [
+        (r#""rate": 10.0"#, r#""rate": 0.0"#),
+        (r#""length": 20.0"#, r#""length": -20.0"#),
+        (r#""elevation_click": 0.1"#, r#""elevation_click": 0.0"#),
+        (r#""caliber": 0.308"#, r#""caliber": 0.0"#),
+    ] {
+        assert!(saved.contains(valid));
+        assert!(matches!(profile_from_str(&saved.replace(valid, invalid), ProfileFormat::Json), Err(ProfileStoreError::Parse(_))));
+    }
+}
@@ -0,0 +1,437 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    CalculatorError,
+    CustomDragModel,
+    DragModel,
+    IntegrationMethod,
+    Twist,
+    TwistDirection,
+    Environment,
+    Projectile,
+    StabilityWarning,
+    StandardDragModel,
+    TrajectoryCalculator,
+    TrajectoryPoint,
+    PressureReference,
+    point_at_distance,
+    ZeroSettings,
+    WindProfile,
+    WindZone,
+};
+
+/// ICAO standard sea level atmosphere, no wind.
+fn standard_atmosphere() -> Environment {
+    Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+}
+
+/// .308 175gr SMK on its G7 BC, the load the effect tests fly.
+fn smk_175(environment: Environment) -> TrajectoryCalculator {
+    let projectile = Projectile::new(175.0, 0.243, 2600.0, 0.308, 1.24).unwrap();
+    TrajectoryCalculator::new(projectile, environment, StandardDragModel::G7.to_model())
+}
+
+/// Flies the calculator out to `yards` and returns the impact there.
+fn impact_at(calculator: &TrajectoryCalculator, yards: f64) -> TrajectoryPoint {
+    let points = calculator.calculate_trajectory(yards + 10.0, 0.001).unwrap();
+    point_at_distance(&points, yards).expect("range not reached")
+}
+
+fn zeroed_trajectory(weight: f64, bc: f64, mv: f64, model: StandardDragModel) -> Vec<TrajectoryPoint> {
+    let projectile = Projectile::new(weight, bc, mv, 0.308, 1.215).unwrap();
+    TrajectoryCalculator::new(projectile, standard_atmosphere(), model.to_model())
+        .with_zero(ZeroSettings::new(1.5, 100.0).unwrap())
+        .calculate_trajectory(1000.0, 0.001)
+        .unwrap()
+}
+
+#[test]
+fn constant_cd_velocity_decay_matches_analytic_solution() {
+    // With a constant Cd, flat fire decays as v(x) = v0·exp(-k·x), k = ρ·Cd·π / (1152·SD)
+    let drag = CustomDragModel::new("constant", vec![(0.0, 0.3), (5.0, 0.3)]).unwrap();
+    let projectile = Projectile::without_ballistic_coefficient(168.0, 2700.0, 0.308, 1.215).unwrap();
+    let k = standard_atmosphere().air_density() * 0.3 * std::f64::consts::PI
+        / (1152.0 * projectile.sectional_density());
+
+    let points = TrajectoryCalculator::new(projectile, standard_atmosphere(), Box::new(drag))
+        .calculate_trajectory(500.0, 0.001)
+        .unwrap();
+
+    for yards in [100.0, 300.0, 500.0] {
+        let expected = 2700.0 * (-k * yards * 3.0).exp();
+        assert_relative_eq!(point_at_distance(&points, yards).unwrap().velocity, expected, max_relative = 0.002);
+    }
+}
+
+#[test]
+fn negligible_drag_drops_like_vacuum() {
+    let drag = CustomDragModel::new("vacuum", vec![(0.0, 1e-9), (5.0, 1e-9)]).unwrap();
+    let projectile = Projectile::without_ballistic_coefficient(168.0, 3000.0, 0.308, 1.215).unwrap();
+
+    let points = TrajectoryCalculator::new(projectile, standard_atmosphere(), Box::new(drag))
+        .calculate_trajectory(600.0, 0.001)
+        .unwrap();
+
+    let point = point_at_distance(&points, 600.0).unwrap();
+    let time = 1800.0 / 3000.0;
+    assert_relative_eq!(point.time, time, max_relative = 1e-4);
+    assert_relative_eq!(point.drop, -0.5 * 32.174 * time * time * 12.0, max_relative = 1e-3);
+}
+
+#[test]
+fn g1_308_168gr_matches_reference() {
+    // .308 Win 168gr, G1 BC 0.462 at 2650 fps, 1.5" sight height, 100 yard zero
+    let points = zeroed_trajectory(168.0, 0.462, 2650.0, StandardDragModel::G1);
+
+    let reference = [
+        // yards, drop (in), velocity (ft/s)
+        (200.0, -4.3, 2272.0),
+        (300.0, -15.3, 2095.0),
+        (500.0, -62.7, 1766.0),
+        (800.0, -224.0, 1353.0),
+        (1000.0, -422.0, 1153.0),
+    ];
+    for (yards, drop, velocity) in reference {
+        let point = point_at_distance(&points, yards).unwrap();
+        assert_relative_eq!(point.drop, drop, max_relative = 0.02);
+        assert_relative_eq!(point.velocity, velocity, max_relative = 0.01);
+    }
+}
+
+#[test]
+fn g7_308_175gr_matches_reference() {
+    // .308 Win 175gr, G7 BC 0.243 at 2600 fps, 1.5" sight height, 100 yard zero
+    let points = zeroed_trajectory(175.0, 0.243, 2600.0, StandardDragModel::G7);
+
+    let reference = [
+        (300.0, -15.9, 2073.0),
+        (600.0, -104.7, 1610.0),
+        (1000.0, -432.0, 1088.0),
+    ];
+    for (yards, drop, velocity) in reference {
+        let point = point_at_distance(&points, yards).unwrap();
+        assert_relative_eq!(point.drop, drop, max_relative = 0.02);
+        assert_relative_eq!(point.velocity, velocity, max_relative = 0.01);
+    }
+}
+
+#[test]
+fn zeroed_trajectory_crosses_line_of_sight_at_zero_range() {
+    let points = zeroed_trajectory(168.0, 0.462, 2650.0, StandardDragModel::G1);
+
+    assert_relative_eq!(points[0].drop, -1.5, epsilon = 0.1);
+    assert!(point_at_distance(&points, 100.0).unwrap().drop.abs() < 0.02);
+    assert!(point_at_distance(&points, 50.0).unwrap().drop > points[0].drop);
+}
+
+/// Counts drag evaluations, i.e. the work an integrator does.
+struct CountingDrag {
+    model: Box<dyn DragModel>,
+    calls: Rc<Cell<usize>>,
+}
+
+impl DragModel for CountingDrag {
+    fn calculate_drag(&self, velocity: f64, air_density: f64, speed_of_sound: f64, ballistic_coefficient: f64) -> f64 {
+        self.calls.set(self.calls.get() + 1);
+        self.model.calculate_drag(velocity, air_density, speed_of_sound, ballistic_coefficient)
+    }
+
+    fn get_cd(&self, mach: f64) -> f64 {
+        self.model.get_cd(mach)
+    }
+}
+
+#[test]
+fn integrators_converge_as_the_step_shrinks() {
+    // Drop one second into flight, a time every step size lands on exactly
+    let drop_after_one_second = |method: IntegrationMethod, step_size: f64| {
+        let calls = Rc::new(Cell::new(0));
+        let drag = CountingDrag { model: StandardDragModel::G7.to_model(), calls: calls.clone() };
+        let projectile = Projectile::new(175.0, 0.243, 2600.0, 0.308, 1.24).unwrap();
+        let points = TrajectoryCalculator::new(projectile, standard_atmosphere(), Box::new(drag))
+            .with_integrator(method.to_integrator())
+            .calculate_trajectory(1000.0, step_size)
+            .unwrap();
+        let point = points.iter().find(|p| (p.time - 1.0).abs() < 1e-9).unwrap();
+        (point.drop, points.len(), calls.get())
+    };
+    let (reference, reference_points, rk4_calls) = drop_after_one_second(IntegrationMethod::RungeKutta4, 0.001);
+
+    // Euler is first order: halving the step halves the error
+    let errors: Vec<f64> = [0.01, 0.005, 0.0025]
+        .iter()
+        .map(|&step| (drop_after_one_second(IntegrationMethod::Euler, step).0 - reference).abs())
+        .collect();
+    assert!(errors.windows(2).all(|pair| pair[1] < 0.6 * pair[0]));
+
+    let (coarse, _, _) = drop_after_one_second(IntegrationMethod::RungeKutta4, 0.01);
+    assert_relative_eq!(coarse, reference, epsilon = 1e-4);
+
+    // RK45 reports at the same spacing while taking far fewer, longer steps
+    let (adaptive, adaptive_points, adaptive_calls) = drop_after_one_second(IntegrationMethod::DormandPrince, 0.001);
+    assert_relative_eq!(adaptive, reference, epsilon = 1e-3);
+    assert_eq!(adaptive_points, reference_points);
+    assert!(adaptive_calls * 4 < rk4_calls);
+}
+
+#[test]
+fn speed_of_sound_follows_temperature() {
+    let cold = Environment::new(0.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+    let hot = Environment::new(100.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+
+    assert_relative_eq!(standard_atmosphere().speed_of_sound(), 1116.45, max_relative = 1e-4);
+    assert_relative_eq!(cold.speed_of_sound(), 1051.0, max_relative = 1e-3);
+    assert_relative_eq!(hot.speed_of_sound(), 1159.7, max_relative = 1e-3);
+}
+
+#[test]
+fn barometric_pressure_is_corrected_to_station_pressure_by_altitude() {
+    let barometric = Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 5000.0, 0.0)
+        .unwrap()
+        .with_pressure_reference(PressureReference::Barometric);
+
+    // ICAO standard pressure at 5000 ft is 24.90 inHg
+    assert_relative_eq!(barometric.station_pressure(), 24.90, max_relative = 1e-3);
+    assert!(barometric.air_density() < standard_atmosphere().air_density());
+    assert!(standard_atmosphere().air_density_at(1000.0) < standard_atmosphere().air_density());
+}
+
+#[test]
+fn density_altitude_round_trips_through_standard_atmosphere() {
+    assert!(Environment::icao_standard(0.0).density_altitude().abs() < 1.0);
+    assert_relative_eq!(Environment::from_density_altitude(6500.0).density_altitude(), 6500.0, epsilon = 1.0);
+
+    // Army Standard Metro is slightly less dense than ICAO at sea level
+    let metro = Environment::army_standard_metro(0.0);
+    assert_relative_eq!(metro.air_density(), 0.0751, max_relative = 0.005);
+    assert!(metro.density_altitude() > 0.0);
+}
+
+#[test]
+fn coriolis_deflects_right_in_the_north_and_up_firing_east() {
+    let impact = |latitude: f64, azimuth: f64| {
+        let environment = Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, latitude).unwrap();
+        let calculator = smk_175(environment)
+            .with_azimuth(azimuth)
+            .with_zero(ZeroSettings::new(1.5, 100.0).unwrap());
+        impact_at(&calculator, 1000.0)
+    };
+    let north = impact(45.0, 0.0);
+    let east = impact(45.0, 90.0);
+    let west = impact(45.0, 270.0);
+    let south = impact(-45.0, 90.0);
+
+    // McCoy's flat fire estimate Ω·X·t·sin(latitude); drag lengthens the flight and adds to it
+    let flat_fire = 7.292115e-5 * 3000.0 * east.time * 45.0_f64.to_radians().sin() * 12.0;
+    assert!(east.coriolis_horizontal > flat_fire && east.coriolis_horizontal < 1.2 * flat_fire);
+    assert_relative_eq!(south.coriolis_horizontal, -east.coriolis_horizontal, max_relative = 1e-9);
+    assert_relative_eq!(east.windage, east.coriolis_horizontal, max_relative = 1e-9);
+
+    // Eötvös: firing east the bullet weighs less, firing west more. The zero is solved without
+    // it, so the full deflection shows up in the drop.
+    assert!(east.coriolis_vertical > 0.0);
+    assert_relative_eq!(west.coriolis_vertical, -east.coriolis_vertical, max_relative = 1e-9);
+    assert_relative_eq!(east.drop - north.drop, east.coriolis_vertical, epsilon = 1e-6);
+    assert_relative_eq!(west.drop - north.drop, west.coriolis_vertical, epsilon = 1e-6);
+}
+
+#[test]
+fn miller_stability_matches_published_value() {
+    // Miller's rule for a 168gr SMK (1.215") from a 1:12 barrel at 2800 fps in standard air is Sg 1.74
+    let projectile = Projectile::new(168.0, 0.462, 2800.0, 0.308, 1.215).unwrap();
+    let twist = Twist::new(12.0, TwistDirection::Right).unwrap();
+    assert_relative_eq!(projectile.miller_stability(&twist, &standard_atmosphere()), 1.74, max_relative = 0.005);
+
+    // Sg scales with the inverse square of the twist and rises in thinner air
+    let faster = Twist::new(10.0, TwistDirection::Right).unwrap();
+    assert_relative_eq!(
+        projectile.miller_stability(&faster, &standard_atmosphere()),
+        1.44 * projectile.miller_stability(&twist, &standard_atmosphere()),
+        max_relative = 1e-9
+    );
+    let thin_air = Environment::icao_standard(5000.0);
+    assert!(projectile.miller_stability(&twist, &thin_air) > projectile.miller_stability(&twist, &standard_atmosphere()));
+
+    let slow = Twist::new(16.0, TwistDirection::Right).unwrap();
+    assert!(matches!(StabilityWarning::from_stability(projectile.miller_stability(&slow, &standard_atmosphere())), Some(StabilityWarning::Unstable(_))));
+}
+
+#[test]
+fn spin_drift_follows_twist_direction() {
+    let impact = |direction: TwistDirection| {
+        let calculator = smk_175(standard_atmosphere()).with_twist(Twist::new(10.0, direction).unwrap());
+        (impact_at(&calculator, 1000.0), calculator.stability().unwrap())
+    };
+    let (right, sg) = impact(TwistDirection::Right);
+    let (left, _) = impact(TwistDirection::Left);
+
+    // Litz: 1.25·(Sg + 1.2)·t^1.83 inches toward the twist, right for a right-hand barrel.
+    // The impact is interpolated between output points, hence the tolerance.
+    assert_relative_eq!(right.spin_drift, 1.25 * (sg + 1.2) * right.time.powf(1.83), max_relative = 1e-6);
+    assert!(right.spin_drift > 0.0);
+    assert_relative_eq!(left.spin_drift, -right.spin_drift, max_relative = 1e-9);
+    assert_relative_eq!(right.windage, right.spin_drift, max_relative = 1e-9);
+    assert_relative_eq!(left.windage, left.spin_drift, max_relative = 1e-9);
+}
+
+#[test]
+fn inclined_holds_follow_the_improved_riflemans_rule() {
+    let impact = |line_of_sight_angle: f64, yards: f64| {
+        let calculator = smk_175(standard_atmosphere())
+            .with_line_of_sight_angle(line_of_sight_angle)
+            .unwrap()
+            .with_zero(ZeroSettings::new(1.5, 100.0).unwrap());
+        impact_at(&calculator, yards)
+    };
+    let hold = |point: &TrajectoryPoint| -point.moa_adjustment().0;
+    let (sin, cos) = 30.0_f64.to_radians().sin_cos();
+
+    for yards in [300.0, 600.0, 1000.0] {
+        let flat = impact(0.0, yards);
+        let uphill = impact(30.0, yards);
+        let downhill = impact(-30.0, yards);
+        // A bullet below the inclined sight line lies further out horizontally
+        assert_relative_eq!(uphill.horizontal_distance, uphill.distance * cos - uphill.drop / 36.0 * sin, epsilon = 0.05);
+
+        // The rifleman's rule holds for the horizontal range and under-holds at long range
+        let riflemans = hold(&impact(0.0, yards * cos));
+        assert!(hold(&uphill) > riflemans && hold(&downhill) > riflemans);
+        assert!(hold(&uphill) < hold(&flat) && hold(&downhill) < hold(&flat));
+
+        // The improved rule scales only the gravity drop below the bore line by cos(angle)
+        let improved = -(flat.drop + (1.0 - cos) * flat.elevation_correction) / (yards * 1.047) * 100.0;
+        assert_relative_eq!(hold(&uphill), improved, max_relative = 0.01);
+        assert_relative_eq!(hold(&downhill), improved, max_relative = 0.01);
+
+        // Gravity slows the bullet climbing and speeds it descending
+        assert!(hold(&uphill) > hold(&downhill));
+    }
+}
+
+#[test]
+fn steep_shots_stop_once_they_fall_back() {
+    assert!(matches!(
+        smk_175(standard_atmosphere()).with_line_of_sight_angle(90.0),
+        Err(CalculatorError::InvalidLineOfSightAngle(_))
+    ));
+    assert!(smk_175(standard_atmosphere()).with_line_of_sight_angle(-90.0).is_err());
+
+    // Nearly straight up the bullet tops out well short of 10000 yards along the sight line
+    let points = smk_175(standard_atmosphere())
+        .with_line_of_sight_angle(89.0)
+        .unwrap()
+        .calculate_trajectory(10000.0, 0.01)
+        .unwrap();
+    assert!(points.last().unwrap().distance < 5000.0);
+}
+
+#[test]
+fn cant_swings_the_dialed_elevation_without_moving_the_zero() {
+    let trajectory = |cant: f64| {
+        smk_175(standard_atmosphere())
+            .with_cant(cant)
+            .with_zero(ZeroSettings::new(1.5, 100.0).unwrap())
+            .calculate_trajectory(1000.0, 0.001)
+            .unwrap()
+    };
+    let level = trajectory(0.0);
+    let canted = trajectory(20.0);
+    let (sin, cos) = 20.0_f64.to_radians().sin_cos();
+
+    for yards in [100.0, 500.0, 1000.0] {
+        let level = point_at_distance(&level, yards).unwrap();
+        let canted = point_at_distance(&canted, yards).unwrap();
+
+        // The correction dialed for this range swings right by its sine and loses height by its cosine
+        assert_relative_eq!(canted.cant_horizontal, canted.elevation_correction * sin, max_relative = 1e-9);
+        assert_relative_eq!(canted.cant_vertical, canted.elevation_correction * (cos - 1.0), max_relative = 1e-9);
+        assert!(canted.cant_horizontal > 0.0 && canted.cant_vertical < 0.0);
+
+        // The zero is solved level, so the canted shot is the level one plus the full cant shift
+        assert_relative_eq!(canted.elevation_correction, level.elevation_correction, epsilon = 1e-9);
+        assert_relative_eq!(canted.drop, level.drop + canted.cant_vertical, epsilon = 1e-9);
+        assert_relative_eq!(canted.windage, level.windage + canted.cant_horizontal, epsilon = 1e-9);
+    }
+    assert!(point_at_distance(&level, 100.0).unwrap().drop.abs() < 0.02);
+}
+
+#[test]
+fn crosswind_drift_follows_lag_rule() {
+    // Didion's lag rule: drift = crosswind · (time of flight − range / muzzle velocity)
+    let environment = Environment::new(59.0, 29.92, 0.0, 10.0, 90.0, 0.0, 0.0).unwrap();
+    let points = smk_175(environment)
+        .calculate_trajectory(1000.0, 0.001)
+        .unwrap();
+
+    for yards in [300.0, 600.0, 1000.0] {
+        let point = point_at_distance(&points, yards).unwrap();
+        let lag = point.time - yards * 3.0 / 2600.0;
+        assert_relative_eq!(point.windage, 10.0 * 5280.0 / 3600.0 * lag * 12.0, max_relative = 0.01);
+    }
+}
+
+#[test]
+fn aerodynamic_jump_survives_the_zero() {
+    let impact = |wind_angle: f64, aerodynamic_jump: bool| {
+        let environment = Environment::new(59.0, 29.92, 0.0, 10.0, wind_angle, 0.0, 0.0).unwrap();
+        let calculator = smk_175(environment)
+            .with_twist(Twist::new(10.0, TwistDirection::Right).unwrap())
+            .with_aerodynamic_jump(aerodynamic_jump)
+            .with_zero(ZeroSettings::new(1.5, 100.0).unwrap());
+        impact_at(&calculator, 1000.0)
+    };
+    let from_left = impact(90.0, true);
+    let from_right = impact(270.0, true);
+
+    // With right-hand twist a wind from the left throws the bullet up and one from the right down
+    assert!(from_left.aerodynamic_jump > 0.0);
+    assert_relative_eq!(from_right.aerodynamic_jump, -from_left.aerodynamic_jump, max_relative = 1e-9);
+    assert_relative_eq!(from_left.drop - from_right.drop, 2.0 * from_left.aerodynamic_jump, epsilon = 0.05);
+    assert_relative_eq!(from_left.drop - impact(90.0, false).drop, from_left.aerodynamic_jump, epsilon = 0.05);
+    assert_relative_eq!(impact(90.0, false).drop, impact(270.0, false).drop, epsilon = 0.05);
+}
+
+#[test]
+fn wind_zones_each_contribute_their_share_of_drift() {
+    let drift = |environment: Environment| impact_at(&smk_175(environment), 1000.0).windage;
+    let zoned = |near: f64, far: f64| {
+        let zones = vec![WindZone::new(0.0, near, 90.0).unwrap(), WindZone::new(500.0, far, 90.0).unwrap()];
+        standard_atmosphere().with_wind_profile(WindProfile::new(zones).unwrap())
+    };
+
+    let full = drift(Environment::new(59.0, 29.92, 0.0, 10.0, 90.0, 0.0, 0.0).unwrap());
+    let near = drift(zoned(10.0, 0.0));
+    let far = drift(zoned(0.0, 10.0));
+
+    // Crosswind drift is linear in the wind, and wind near the muzzle matters more
+    assert_relative_eq!(near + far, full, max_relative = 0.005);
+    assert!(near > far);
+    assert!(WindProfile::new(vec![WindZone::new(100.0, 10.0, 90.0).unwrap()]).is_err());
+
+    // Deserializing checks the zones like the constructors, so an empty profile cannot reach `zone_at`
+    let loaded: WindProfile = serde_json::from_str(r#"{"zones": [{"start": 0.0, "speed": 10.0, "angle": 90.0}]}"#).unwrap();
+    assert_eq!(loaded.zone_at(500.0).speed, 10.0);
+    assert!(serde_json::from_str::<WindProfile>(r#"{"zones": []}"#).is_err());
+    assert!(serde_json::from_str::<WindProfile>(r#"{"zones": [{"start": 100.0, "speed": 10.0, "angle": 90.0}]}"#).is_err());
+    assert!(serde_json::from_str::<WindZone>(r#"{"start": 0.0, "speed": 10.0, "angle": 400.0}"#).is_err());
+}
+
+#[test]
+fn updraft_lifts_impact_by_reported_vertical_wind_drift() {
+    let impact = |vertical_wind: f64, zeroed: bool| {
+        let mut calculator = smk_175(standard_atmosphere().with_vertical_wind(vertical_wind));
+        if zeroed {
+            calculator = calculator.with_zero(ZeroSettings::new(1.5, 100.0).unwrap());
+        }
+        impact_at(&calculator, 1000.0)
+    };
+
+    // The zero is solved in still air, so it does not absorb any of the updraft
+    for zeroed in [false, true] {
+        let still = impact(0.0, zeroed);
+        let updraft = impact(10.0, zeroed);
+        assert_relative_eq!(updraft.drop - still.drop, updraft.vertical_wind, max_relative = 0.01);
+        assert!(updraft.vertical_wind > 0.0);
+    }
+}
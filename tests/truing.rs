@@ -0,0 +1,42 @@
+use approx::assert_relative_eq;
+use ballistic_calculator::{
+    DropObservation,
+    Environment,
+    point_at_distance,
+    Projectile,
+    StandardDragModel,
+    TrajectoryCalculator,
+    TruingParameter,
+    ZeroSettings,
+};
+
+/// ICAO standard sea level atmosphere, no wind.
+fn standard_atmosphere() -> Environment {
+    Environment::new(59.0, 29.92, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+}
+
+#[test]
+fn truing_recovers_muzzle_velocity_from_observed_drops() {
+    let calculator = |mv: f64| {
+        let projectile = Projectile::new(168.0, 0.462, mv, 0.308, 1.215).unwrap();
+        TrajectoryCalculator::new(projectile, standard_atmosphere(), StandardDragModel::G1.to_model())
+            .with_zero(ZeroSettings::new(1.5, 100.0).unwrap())
+    };
+    let actual = calculator(2650.0).calculate_trajectory(900.0, 0.001).unwrap();
+    let observations: Vec<DropObservation> = [500.0, 800.0]
+        .iter()
+        .map(|&range| DropObservation { range, drop: point_at_distance(&actual, range).unwrap().drop })
+        .collect();
+
+    let mut trued = calculator(2750.0);
+    let result = trued.true_to_observations(&observations, TruingParameter::MuzzleVelocity, 0.001).unwrap();
+
+    assert_relative_eq!(result.value, 2650.0, epsilon = 2.0);
+    assert!(result.rms_residual() < 0.1);
+    assert_relative_eq!(trued.projectile().muzzle_velocity, result.value);
+
+    // A drop no velocity can explain fails the fit and leaves the calculator as it was
+    let impossible = [DropObservation { range: 800.0, drop: -100000.0 }];
+    assert!(trued.true_to_observations(&impossible, TruingParameter::MuzzleVelocity, 0.001).is_err());
+    assert_relative_eq!(trued.projectile().muzzle_velocity, result.value);
+}